@@ -1,6 +1,6 @@
 import "jsr:@std/dotenv/load"
 export { encodeHex, decodeHex } from "jsr:@std/encoding/hex"
-export { encodeBase64 } from "jsr:@std/encoding/base64"
+export { encodeBase64, decodeBase64 } from "jsr:@std/encoding/base64"
 export { createPool, sql } from 'npm:slonik'
 export type { DatabasePool } from 'npm:slonik'
 export { createPgDriverFactory } from 'npm:@slonik/pg-driver'
@@ -1,9 +1,18 @@
 /** PostgreSQL connection URL. */
 export const IBC_DECODER_DB = Deno.env.get("IBC_DECODER_DB") || 'localhost:5432'
 
+/** CometBFT RPC URL, to fetch the transactions to decode. */
+export const IBC_DECODER_RPC = Deno.env.get("IBC_DECODER_RPC") || 'http://localhost:26657'
+
+/** Whether to keep messages whose nested fields fail to decode, with a list of warnings,
+  * instead of marking the whole section as failed. Set to "true" to enable. */
+export const IBC_DECODER_LENIENT = Deno.env.get('IBC_DECODER_LENIENT') === 'true'
+
 /** Version identifier for this service.
-  * This is stored in the database alongside the decoded data. */
-export const IBC_DECODER_VERSION = Deno.env.get('IBC_DECODER_VERSION') || 'v1'
+  * This is stored in the database alongside the decoded data. Lenient output has
+  * a different shape, so it's stored with a `-lenient` suffix on the version. */
+export const IBC_DECODER_VERSION = (Deno.env.get('IBC_DECODER_VERSION') || 'v1')
+  + (IBC_DECODER_LENIENT ? '-lenient' : '')
 
 /** Milliseconds to wait on completion before checking for new IBC transactions. */
 export const IBC_DECODER_DELAY = Number(Deno.env.get('IBC_DECODER_DELAY')) || 5000
@@ -1,14 +1,17 @@
-import type { IBCCounterData, IBCEventHandlers } from './ibc-events.ts'
+import type { IBCCounterData, IBCEventHandlers, IbcDecodeError } from './ibc-events.ts'
 
 /** Keeps count of decoded IBC transactions. */
 export class IBCCounter implements IBCCounterData {
-  events  = new EventTarget()
-  total   = 0
-  decoded = 0
-  failed  = 0
+  events   = new EventTarget()
+  total    = 0
+  decoded  = 0
+  failed   = 0
   ibcTypes: Record<string, number> = {}
   typeUrls: Set<string> = new Set()
-  errors: Array<[string, unknown]> = []
+  errors: Array<[string, IbcDecodeError]> = []
+  errorKinds: Record<string, number> = {}
+  warnings: Array<[string, IbcDecodeError]> = []
+  warningKinds: Record<string, number> = {}
   countIbcDecodeBegin (): number {
     return this.total++
   }
@@ -20,13 +23,28 @@ export class IBCCounter implements IBCCounterData {
     }
     this.decoded++
   }
-  countIbcDecodeFailure (prefix: string, error: unknown) {
+  countIbcDecodeFailure (prefix: string, error: IbcDecodeError) {
     this.errors.push([prefix, error])
+    countKind(this.errorKinds, error)
     this.failed++
   }
+  /** Count a nested failure in a message that was decoded leniently.
+    * The message itself was decoded, so this is not counted as an error. */
+  countIbcDecodeWarning (prefix: string, warning: IbcDecodeError) {
+    this.warnings.push([prefix, warning])
+    countKind(this.warningKinds, warning)
+  }
   bindEvents (events: IBCEventHandlers = {}) {
     for (const [event, handler] of Object.entries(events)) {
       this.events.addEventListener(event, handler as (event: Event)=>unknown)
     }
   }
 }
+
+/** Count an error by `${stage}/${kind}`. */
+function countKind (kinds: Record<string, number>, error: IbcDecodeError) {
+  // Errors that didn't come from the decoder (e.g. a panic) have no kind.
+  const kind = `${error.stage ?? 'unknown'}/${error.kind ?? 'unknown'}`
+  kinds[kind] ??= 0
+  kinds[kind]++
+}
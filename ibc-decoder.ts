@@ -1,63 +1,97 @@
 import { IBCCounter } from './ibc-counter.ts'
 import { IBCDecodeProgress, IBCDecodeSuccess, IBCDecodeFailure } from './ibc-events.ts'
-import type { DecodedIBC } from './ibc-events.ts'
+import type { DecodedTxEntry, IbcDecodeError } from './ibc-events.ts'
+// @deno-types="./pkg/namada_ibc_decoder.d.ts"
 import initDecoder, { Decode } from './pkg/namada_ibc_decoder.js'
-import { decodeHex } from './deps.ts'
-
-interface DecoderWASM {decode_ibc: (bin: Uint8Array)=>object}
+import type { InitOutput } from './pkg/namada_ibc_decoder.d.ts'
+import { decodeBase64 } from './deps.ts'
+import * as Config from './ibc-config.ts'
 
 /** Decodes IBC transactions using the WASM module. */
 export class IBCDecoder extends IBCCounter {
 
-  decodeTx (tx: TX) {
-    let next_is_ibc = false
-    for (const sectionIndex in tx.data.txsections) {
-      const section = tx.data.txsections[sectionIndex]
-      if (next_is_ibc && (section.type === 'Data')) {
-        this.decodeIbc(tx.data.blockHeight, tx.data.txHash, sectionIndex, decodeHex(section.data))
+  /** Fetch the transaction from its block, and decode the given IBC inner transactions in it.
+    * Each entry's `index` is the position of the inner transaction in the batch,
+    * which is also its index in the transaction's `content`. */
+  async decodeTx (tx: TX) {
+    const { blockHeight, txHash, contentIndices } = tx.data
+    let entries: DecodedTxEntry[]
+    try {
+      const binary = await IBCDecoder.fetchTx(blockHeight, txHash)
+      entries = Decode.tx(binary, { lenient: Config.IBC_DECODER_LENIENT })
+    } catch (e: unknown) {
+      // The whole transaction failed, so each of its inner transactions did.
+      entries = contentIndices.map(index=>({
+        index, codeHash: '', dataHash: '', ok: false, error: e as IbcDecodeError
+      }))
+    }
+    for (const entry of entries) {
+      if (contentIndices.includes(entry.index)) {
+        this.decodeEntry(blockHeight, txHash, entry)
       }
-      next_is_ibc = (section.type === 'Code') && (section.tag === 'tx_ibc.wasm')
     }
   }
 
-  decodeIbc (blockHeight: number, txHash: string, sectionIndex: string, binary: Uint8Array) {
+  decodeEntry (blockHeight: number, txHash: string, entry: DecodedTxEntry) {
     const ibcIndex = this.countIbcDecodeBegin()
-    try {
-      const decoded = Decode.ibc(binary) as DecodedIBC
-      this.countIbcDecodeSuccess(decoded.type, decoded?.clientMessage?.typeUrl)
+    const { index: contentIndex, dataHash } = entry
+    const prefix = this.logPrefix(txHash, contentIndex)
+    if (entry.ok) {
+      const { decoded } = entry
+      const typeUrl = (decoded.type === 'envelope.client.update') ? decoded.clientMessage.typeUrl : undefined
+      this.countIbcDecodeSuccess(decoded.type, typeUrl)
+      for (const warning of decoded.warnings ?? []) {
+        this.countIbcDecodeWarning(prefix, warning)
+      }
       this.events.dispatchEvent(new IBCDecodeSuccess({
         context: this,
         ibcIndex,
         blockHeight,
         txHash,
-        sectionIndex,
-        binary,
+        contentIndex,
+        dataHash,
         decoded,
       }))
-    } catch (e: unknown) {
-      const error = e as { message: string }
-      this.countIbcDecodeFailure(this.logPrefix(txHash, sectionIndex, binary.length), error)
+    } else {
+      const { error } = entry
+      this.countIbcDecodeFailure(prefix, error)
       this.events.dispatchEvent(new IBCDecodeFailure({
         context: this,
         ibcIndex,
         blockHeight,
         txHash,
-        sectionIndex,
-        binary,
+        contentIndex,
+        dataHash,
         error
       }))
     }
     this.events.dispatchEvent(new IBCDecodeProgress({...this}))
   }
 
-  logPrefix = (txHash: string, sectionIndex: string, length: number) =>
-    `IBC#${this.total}: TX: ${txHash} Section: ${sectionIndex}: ${length}b:`
+  logPrefix = (txHash: string, contentIndex: number) =>
+    `IBC#${this.total}: TX: ${txHash} Content: ${contentIndex}:`
+
+  /** Fetch the borsh-encoded transaction with the given hash from its block. */
+  static async fetchTx (blockHeight: number, txHash: string): Promise<Uint8Array> {
+    const response = await fetch(`${Config.IBC_DECODER_RPC}/block?height=${blockHeight}`)
+    if (!response.ok) {
+      throw new Error(`Could not fetch block ${blockHeight}: ${response.status} ${response.statusText}`)
+    }
+    const { result } = await response.json()
+    for (const tx of result.block.data.txs ?? []) {
+      const binary = decodeBase64(tx)
+      if (Decode.txHash(binary).toLowerCase() === txHash.toLowerCase()) {
+        return binary
+      }
+    }
+    throw new Error(`Could not find tx ${txHash} in block ${blockHeight}`)
+  }
 
   /** Handle to the WASM decoder. */
-  static decoder: DecoderWASM
+  static decoder: InitOutput
 
   /** Load the WASM decoder if not loaded. */
-  static initDecoder (): Promise<DecoderWASM> {
+  static initDecoder (): Promise<InitOutput> {
     if (this.decoder) {
       return Promise.resolve(this.decoder)
     } else {
@@ -76,6 +110,11 @@ export class IBCDecoder extends IBCCounter {
 
 }
 
-export interface TX { data: { blockHeight: number, txHash: string, txsections: Array<TXSection> } }
-
-export interface TXSection { type: string, tag: string, data: string, }
+export interface TX {
+  data: {
+    blockHeight:    number,
+    txHash:         string,
+    /** Indices of the undecoded IBC inner transactions in the transaction's `content`. */
+    contentIndices: number[]
+  }
+}
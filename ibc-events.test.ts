@@ -0,0 +1,65 @@
+import { assert, assertEquals } from '@std/assert'
+import { ibcSerialize } from './ibc-events.ts'
+import { IBCDecoder } from './ibc-decoder.ts'
+// @deno-types="./pkg/namada_ibc_decoder.d.ts"
+import { Decode } from './pkg/namada_ibc_decoder.js'
+
+await IBCDecoder.initDecoder()
+
+/** Encode a length-delimited protobuf field. */
+function field (number: number, value: string|Uint8Array): number[] {
+  const bytes = (typeof value === 'string') ? new TextEncoder().encode(value) : value
+  const length = []
+  for (let n = bytes.length; ; n >>= 7) {
+    if (n < 0x80) { length.push(n); break }
+    length.push((n & 0x7f) | 0x80)
+  }
+  return [(number << 3) | 2, ...length, ...bytes]
+}
+
+/** Encode an `Any`. */
+function any (typeUrl: string, value: number[]): Uint8Array {
+  return new Uint8Array([...field(1, typeUrl), ...field(2, new Uint8Array(value))])
+}
+
+Deno.test('lenient failures keep their message when serialized', () => {
+  // A client update whose Tendermint header isn't valid protobuf.
+  const header = any('/ibc.lightclients.tendermint.v1.Header', [0xff])
+  const message = any('/ibc.core.client.v1.MsgUpdateClient', [
+    ...field(1, '07-tendermint-0'),
+    ...field(2, header),
+    ...field(3, 'tnam1qrxsru5rdu4he400xny6p779fcw7xuftsgjnmzup'),
+  ])
+  const decoded = Decode.ibc(message, { lenient: true })
+  const json = JSON.parse(ibcSerialize(decoded))
+  const { error } = json.clientMessage.value
+  assert(error.message, `no message in ${JSON.stringify(error)}`)
+  assertEquals(error.kind, 'protobuf')
+  assertEquals(json.warnings.length, 1)
+  assert(json.warnings[0].message, `no message in ${JSON.stringify(json.warnings[0])}`)
+  assertEquals(json.warnings[0].path, 'clientMessage.value')
+})
+
+Deno.test('errors thrown from JS keep their message when serialized', () => {
+  const error = Object.assign(new Error('failed'), { kind: 'conversion' })
+  assertEquals(JSON.parse(ibcSerialize({ error })), { error: { kind: 'conversion', message: 'failed' } })
+})
+
+Deno.test('batch items fail on their own, in input order', () => {
+  const message = any('/ibc.core.client.v1.MsgUpdateClient', [
+    ...field(1, '07-tendermint-0'),
+    ...field(2, any('/test.v1.Sample', [4, 5, 6])),
+    ...field(3, 'tnam1qrxsru5rdu4he400xny6p779fcw7xuftsgjnmzup'),
+  ])
+  const results = Decode.ibcBatch([message, new Uint8Array([0xff]), 'not bytes', message])
+  assertEquals(results.map(result => result.ok), [true, false, false, true])
+  const [valid, corrupt, invalid, last] = results
+  assert(valid.ok && last.ok)
+  assertEquals(valid.value.type, 'envelope.client.update')
+  assertEquals(last.value, valid.value)
+  assert(!corrupt.ok && !invalid.ok)
+  assert(corrupt.error.message, `no message in ${JSON.stringify(corrupt.error)}`)
+  assertEquals(invalid.error.kind, 'invalid-input')
+  assertEquals(invalid.error.stage, 'input')
+  assert(invalid.error.message.includes('batch item 2'), invalid.error.message)
+})
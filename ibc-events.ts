@@ -1,6 +1,7 @@
 // deno-lint-ignore-file no-explicit-any
 
 import { encodeBase64 } from './deps.ts'
+import type { DecodedIbc as DecodedIBC, IbcDecodeError } from './pkg/namada_ibc_decoder.d.ts'
 
 export function ibcSerialize (data: object, indent?: number) {
   return JSON.stringify(data, ibcSerializer, indent)
@@ -9,6 +10,8 @@ export function ibcSerialize (data: object, indent?: number) {
 export function ibcSerializer (_: unknown, value: unknown) {
   if (value instanceof Uint8Array) return encodeBase64(value)
   if (typeof value === 'bigint') return String(value)
+  // Errors thrown from JS keep their message, which isn't enumerable, and their extra fields.
+  if (value instanceof Error) return { ...value, message: value.message }
   return value
 }
 
@@ -18,7 +21,13 @@ export interface IBCCounterData {
   failed:   number
   ibcTypes: Record<string, number>
   typeUrls: Set<string>
-  errors:   Array<[string, unknown]>
+  errors:   Array<[string, IbcDecodeError]>
+  /** Count of failures by `${stage}/${kind}` of the error. */
+  errorKinds: Record<string, number>
+  /** Nested failures in messages that were decoded leniently. */
+  warnings: Array<[string, IbcDecodeError]>
+  /** Count of warnings by `${stage}/${kind}` of the error. */
+  warningKinds: Record<string, number>
 }
 
 export interface IBCDecodeData<T extends IBCCounterData> {
@@ -26,22 +35,19 @@ export interface IBCDecodeData<T extends IBCCounterData> {
   ibcIndex:     number,
   blockHeight:  number,
   txHash:       string,
-  sectionIndex: string,
-  binary:       Uint8Array
+  /** Index of the inner transaction in the batch, and in the `content` of the transaction. */
+  contentIndex: number,
+  dataHash:     string
 }
 
 export interface IBCDecodeSuccessData<T extends IBCCounterData> extends IBCDecodeData<T> {
   decoded: DecodedIBC
 }
 
-export interface DecodedIBC {
-  type: string,
-  clientMessage?: { typeUrl?: string },
-  [k: string]: unknown
-}
+export type { DecodedIbc as DecodedIBC, DecodedTxEntry, IbcDecodeError } from './pkg/namada_ibc_decoder.d.ts'
 
 export interface IBCDecodeFailureData<T extends IBCCounterData> extends IBCDecodeData<T> {
-  error: { message: string }
+  error: IbcDecodeError
 }
 
 export class IBCDecodeProgress extends CustomEvent<IBCCounterData> {
@@ -49,17 +55,21 @@ export class IBCDecodeProgress extends CustomEvent<IBCCounterData> {
     super('decode-progress', { detail })
   }
   report (verbose: boolean = false) {
-    const {total, decoded, failed, ibcTypes, typeUrls} = this.detail
+    const {total, decoded, failed, warnings, ibcTypes, typeUrls, errorKinds, warningKinds} = this.detail
     if (verbose) {
       console.log()
-      console.log(`\n😼 Decoded ${decoded}/${total} (${failed} failed).`)
+      console.log(`\n😼 Decoded ${decoded}/${total} (${failed} failed, ${warnings.length} warnings).`)
       const types = Object.keys(ibcTypes)
       console.log(`\n😼 ${types.length} IBC type(s):\n `, types.join(', '))
       console.log(`\n😼 ${typeUrls.size} type URL(s):\n `, [...typeUrls].join(', '))
+      const kinds = Object.entries(errorKinds).map(([kind, count])=>`${kind}: ${count}`)
+      console.log(`\n😼 ${kinds.length} error kind(s):\n `, kinds.join(', '))
+      const warningKindList = Object.entries(warningKinds).map(([kind, count])=>`${kind}: ${count}`)
+      console.log(`\n😼 ${warningKindList.length} warning kind(s):\n `, warningKindList.join(', '))
       console.log()
     } else {
       console.log(
-        `😼 Decoded ${decoded}/${total} (${failed} failed);`,
+        `😼 Decoded ${decoded}/${total} (${failed} failed, ${warnings.length} warnings);`,
         `IBC types: ${Object.keys(ibcTypes).length};`,
         `Type URLs: ${typeUrls.size}`,
       )
@@ -72,20 +82,20 @@ export class IBCDecodeSuccess<T extends IBCCounterData> extends CustomEvent<IBCD
     super('decode-success', { detail })
   }
   report (verbose: boolean = false) {
-    const {ibcIndex, blockHeight, txHash, sectionIndex, binary, decoded} = this.detail
+    const {ibcIndex, blockHeight, txHash, contentIndex, dataHash, decoded} = this.detail
     if (verbose) {
       console.log()
       console.log('🟢 IBC #:  ', ibcIndex)
       console.log('🟢 Block:  ', blockHeight)
       console.log('🟢 TX ID:  ', txHash)
-      console.log('🟢 Section:', sectionIndex)
-      console.log('🟢 Bytes:  ', binary.length)
+      console.log('🟢 Content:', contentIndex)
+      console.log('🟢 Hash:   ', dataHash)
       console.log('🟢 Data:\n' + JSON.stringify(decoded, ibcSerializer, 2))
     } else {
       console.log(
         '🟢 IBC #', ibcIndex,     'is',     decoded.type,
         'at',       blockHeight,  'tx',     txHash,
-        '#',        sectionIndex, 'bytes:', binary.length
+        '#',        contentIndex, 'data:',  dataHash
       )
     }
   }
@@ -96,13 +106,13 @@ export class IBCDecodeFailure<T extends IBCCounterData> extends CustomEvent<IBCD
     super('decode-failure', { detail })
   }
   report () {
-    const {ibcIndex, blockHeight, txHash, sectionIndex, binary, error} = this.detail
+    const {ibcIndex, blockHeight, txHash, contentIndex, dataHash, error} = this.detail
     console.log()
     console.log('🔴 IBC #:  ', ibcIndex)
     console.log('🔴 Block:  ', blockHeight)
     console.log('🔴 TX ID:  ', txHash)
-    console.log('🔴 Section:', sectionIndex)
-    console.log('🔴 Bytes:  ', binary.length)
+    console.log('🔴 Content:', contentIndex)
+    console.log('🔴 Hash:   ', dataHash)
     console.log('🔴', error)
   }
 }
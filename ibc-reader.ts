@@ -5,18 +5,14 @@ import { sql } from './deps.ts'
 import type { DatabasePool } from './deps.ts'
 import * as Config from './ibc-config.ts'
 
-export const txContentColumn = sql.fragment`
-  jsonb_path_query("txData", '$.data.content[*]') as txContent
-`
-export const txSectionsColumn = sql.fragment`
-  "txData"->'data'->'sections' as txSections
-`
+/** Every entry of the content of every transaction, with its index in the content. */
 export const withIbcTransactionsSubQuery = sql.fragment`with ibc_txs as (select
   "blockHeight",
   "txHash",
-  ${txContentColumn},
-  ${txSectionsColumn}
-  from transactions)`
+  txcontent,
+  (position - 1)::int as "contentIndex"
+  from transactions,
+  jsonb_array_elements("txData"->'data'->'content') with ordinality as content(txcontent, position))`
 export const hasIbcTransactionClause = sql.fragment`
   (txcontent->>'type')::text = 'tx_ibc.wasm'
 `
@@ -25,9 +21,16 @@ export const hasUndecodedIbcTransactionClause = sql.fragment`(
   or
   (txcontent->'data'->>'decoderVersion' != ${Config.IBC_DECODER_VERSION})
 )`
+/** One row per transaction, with the indices of its undecoded IBC content entries,
+  * since the whole transaction is fetched and decoded at once. */
 export const allUndecodedIbcTransactionsQuery = sql.unsafe`
-  ${withIbcTransactionsSubQuery} select * from ibc_txs
+  ${withIbcTransactionsSubQuery} select
+    "blockHeight",
+    "txHash",
+    array_agg("contentIndex" order by "contentIndex") as "contentIndices"
+  from ibc_txs
   where ${hasIbcTransactionClause} and ${hasUndecodedIbcTransactionClause}
+  group by "blockHeight", "txHash"
   order by "blockHeight" desc;
 `
 
@@ -51,16 +54,25 @@ export class IBCReader extends IBCDecoder {
     }
   }
 
-  onStream = (stream: TXStream) => stream.on('data', this.onData)
+  /** Decode one transaction at a time, pausing the stream while each one is
+    * fetched and decoded, so that requests to the RPC don't pile up. */
+  onStream = (stream: TXStream) => stream.on('data', (tx: TX) => {
+    stream.pause()
+    this.onData(tx).finally(()=>stream.resume())
+  })
 
-  onData = (tx: TX) => {
+  onData = async (tx: TX) => {
     //console.log(tx)
-    return this.decodeTx(tx)
+    await this.decodeTx(tx)
   }
 
 }
 
-interface TXStream { on: (event: string, cb: TXCallback) => unknown }
+interface TXStream {
+  on:     (event: string, cb: TXCallback) => unknown
+  pause:  () => unknown
+  resume: () => unknown
+}
 
 type TXCallback = (data: TX) => unknown
 
@@ -1,4 +1,4 @@
-import { sql, encodeHex } from './deps.ts'
+import { sql } from './deps.ts'
 import { IBCReader } from './ibc-reader.ts'
 import { ibcSerialize } from './ibc-events.ts'
 import type { IBCDecodeData, IBCDecodeSuccessData, IBCDecodeFailureData } from './ibc-events.ts'
@@ -21,7 +21,8 @@ export async function updateDecodeFailure (
 ) {
   const updateIndex = await queryContentIndex(detail)
   logFailureBefore(detail, updateIndex, dryRun)
-  const result = { error: detail.error.message }
+  const { message, kind, stage, typeUrl, path, offset } = detail.error
+  const result = { error: message, errorDetails: { kind, stage, typeUrl, path, offset } }
   await updateDecodeResult(detail, result, updateIndex, dryRun)
   logFailureAfter(detail, updateIndex, dryRun)
 }
@@ -45,52 +46,20 @@ export async function updateDecodeResult (
   await context.pool!.one(updateQuery)
 }
 
+/** Check that the inner transaction that was decoded is an IBC one in the stored data.
+  * The entries of `content` are in the order of the batch, like the `index` of the
+  * entries that `Decode.tx` returns. */
 export async function queryContentIndex (detail: IBCDecodeData<IBCReader>) {
-  const selectQuery = sql.unsafe`select * from transactions where "txHash" = ${detail.txHash}`
+  const { blockHeight, txHash, contentIndex } = detail
+  const selectQuery = sql.unsafe`select * from transactions where "txHash" = ${txHash}`
   const { txData: { data } } = await detail.context.pool!.one(selectQuery)
-  const updateIndex = findContentIndex(detail.blockHeight, detail.txHash, data, detail.binary)
-  return updateIndex
-}
-
-export function findContentIndex (
-  blockHeight: number,
-  txHash:      string,
-  data:        TXData,
-  binary:      Uint8Array
-) {
-  // Warning!!! This will fail if the TX section order
-  // deviates from "...Code1, Data1... Code2, Data2..."
-  // At time of writing, @fadroma/namada makes the same assumption.
-  // (see PR https://github.com/hackbg/fadroma/pull/233 by @shurinov)
-  let contentIndex = -1;
-  const encodedBinary = encodeHex(binary)
-  for (const section of data.sections) {
-    if (isCodeSection(section)) {
-      contentIndex++
-    } else if (isIBCDataSection(section, encodedBinary)) {
-      break
-    }
-  }
-  if (contentIndex === -1) {
-    throw new Error(`Could not find matching contentIndex for tx ${txHash} in ${blockHeight}`)
-  }
-  const content = data.content[contentIndex]
+  const content = (data as TXData).content[contentIndex]
   if (content?.type !== 'tx_ibc.wasm') {
     throw new Error(`Could not find tx_ibc.wasm at contentIndex ${contentIndex} for tx ${txHash} in ${blockHeight}`)
   }
   return contentIndex
 }
 
-function isCodeSection (section: TXSection) {
-  return section.type === 'Code'
-}
-function isIBCCodeSection (section: TXSection) {
-  return isCodeSection(section) && (section.tag === 'tx_ibc.wasm')
-}
-function isIBCDataSection (section: TXSection, encodedBinary: string) {
-  return (section.type === 'Data') && (section.data === encodedBinary)
-}
-
 export interface TXData {
   sections: Array<TXSection>
   batch:   Array<{
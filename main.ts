@@ -11,32 +11,32 @@ const reader = new IBCReader({
 
   async 'decode-success' (event) {
     event.report()
-    const {context, txHash, sectionIndex} = event.detail
-    console.log('⏳ Writing result for', txHash, '/', sectionIndex, '...')
+    const {context, txHash, contentIndex} = event.detail
+    console.log('⏳ Writing result for', txHash, '/', contentIndex, '...')
     try {
       await context.pool!.query(sql.unsafe`
         SELECT jsonb_set("txData"->'data'->'sections', path, value)
         FROM transactions WHERE "txHash" = ${event.detail.txHash}
       `)
-      console.log('🟢 Updated', txHash, '/', sectionIndex)
+      console.log('🟢 Updated', txHash, '/', contentIndex)
     } catch (e) {
-      console.error('🔴 Failed to update', txHash, '/', sectionIndex, ':')
+      console.error('🔴 Failed to update', txHash, '/', contentIndex, ':')
       console.error(e)
     }
   },
 
   async 'decode-failure' (event) {
     event.report()
-    const {context, txHash, sectionIndex} = event.detail
-    console.log('⏳ Writing failure for', txHash, '/', sectionIndex, '...')
+    const {context, txHash, contentIndex} = event.detail
+    console.log('⏳ Writing failure for', txHash, '/', contentIndex, '...')
     try {
       await context.pool!.query(sql.unsafe`
         SELECT jsonb_set("txData"->'data'->'sections', path, value)
         FROM transactions WHERE "txHash" = ${event.detail.txHash}
       `)
-      console.log('🟡 Updated', txHash, '/', sectionIndex)
+      console.log('🟡 Updated', txHash, '/', contentIndex)
     } catch (e) {
-      console.error('🔴 Failed to update', txHash, '/', sectionIndex, ':')
+      console.error('🔴 Failed to update', txHash, '/', contentIndex, ':')
       console.error(e)
     }
   },
@@ -45,3 +45,5 @@ const reader = new IBCReader({
 await runWithConnectionPool(reader.run)
 console.log('Errors encountered:')
 for (const error of reader.errors) console.log(' ', error)
+console.log('Warnings encountered:')
+for (const warning of reader.warnings) console.log(' ', warning)
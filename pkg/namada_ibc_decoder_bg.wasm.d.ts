@@ -1,13 +0,0 @@
-/* tslint:disable */
-/* eslint-disable */
-export const memory: WebAssembly.Memory;
-export function __wbg_decode_free(a: number, b: number): void;
-export function decode_ibc(a: number): Array;
-export const __wbindgen_export_0: WebAssembly.Table;
-export function __externref_table_dealloc(a: number): void;
-export function __wbindgen_exn_store(a: number): void;
-export function __externref_table_alloc(): number;
-export function __wbindgen_free(a: number, b: number, c: number): void;
-export function __wbindgen_malloc(a: number, b: number): number;
-export function __wbindgen_realloc(a: number, b: number, c: number, d: number): number;
-export function __wbindgen_start(): void;
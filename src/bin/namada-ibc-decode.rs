@@ -0,0 +1,217 @@
+//! Decode IBC payloads from the command line, and print them as JSON.
+//!
+//! The output has the same structure as `Decode.ibc`, serialized like `ibcSerialize` does.
+
+use std::io::{BufRead, Read, Write};
+use std::process::ExitCode;
+use namada_ibc_decoder::{decode_ibc, DecodeError, Options, options::{ValidatorSets, Format}};
+
+const USAGE: &str = "\
+Usage: namada-ibc-decode [OPTIONS] [--hex | --base64 | --raw] [--file PATH | PAYLOAD]
+       namada-ibc-decode [OPTIONS] --ndjson
+
+Decodes the data section of a Namada IBC transaction, and prints it as JSON.
+The payload is read from the PAYLOAD argument, from PATH, or from stdin.
+If no encoding is given, hex and base64 are detected, and anything else
+read from a file or stdin is treated as raw bytes.
+
+With --ndjson, reads newline-delimited records from stdin, of the form
+  {\"txHash\": ..., \"blockHeight\": ..., \"sectionIndex\": ..., \"data\": \"<hex or base64>\"}
+and writes one record per line to stdout, with \"data\" replaced by
+either \"decoded\" or \"error\". Errors are objects of the form
+  {\"message\", \"kind\", \"stage\", \"typeUrl\", \"path\", \"offset\"}
+
+Options:
+  --validator-sets full|hash  output every validator, or only the hash of the set
+  --format native|proto3      output the decoder's own structure, or canonical proto3 JSON
+  --lenient                   output nested fields that fail to decode as {\"error\", \"raw\"},
+                              and list their errors in \"warnings\", instead of failing
+
+Exit codes:
+  0  decoded successfully (with --ndjson: processed the whole stream)
+  1  payload could not be decoded
+  2  invalid arguments or input";
+
+/// Exit code for when the payload can't be decoded.
+const EXIT_DECODE: u8 = 1;
+
+/// Exit code for when the arguments or input are invalid.
+const EXIT_INPUT: u8 = 2;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Encoding { Auto, Hex, Base64, Raw }
+
+/// Parsed command line arguments.
+#[derive(Default)]
+struct Args {
+    encoding: Option<Encoding>,
+    file:     Option<String>,
+    payload:  Option<String>,
+    ndjson:   bool,
+    options:  Options,
+}
+
+fn main () -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::from(EXIT_INPUT)
+        }
+    };
+    let options = args.options.clone();
+    if args.ndjson {
+        return match options.scope(||decode_stream(std::io::stdin().lock(), std::io::stdout().lock())) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("Failed to process stream: {error}");
+                ExitCode::from(EXIT_INPUT)
+            }
+        }
+    }
+    let bytes = match read_input(args) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            return ExitCode::from(EXIT_INPUT)
+        }
+    };
+    match options.scope(||decode_ibc(&bytes)) {
+        Ok(decoded) => {
+            println!("{:#}", decoded.to_json());
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            eprintln!("Failed to decode {} bytes: {error}", bytes.len());
+            ExitCode::from(EXIT_DECODE)
+        }
+    }
+}
+
+/// Decode a stream of NDJSON records, one line at a time.
+///
+/// Each output record keeps every field of the input record except `data`,
+/// which is replaced with `decoded` or `error`, a structured [`DecodeError`].
+/// Lines that aren't valid records produce an error record with the line number,
+/// and don't stop the stream.
+fn decode_stream (input: impl BufRead, output: impl Write) -> std::io::Result<()> {
+    use serde_json::Value as Json;
+    let mut output = std::io::BufWriter::new(output);
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let record = match serde_json::from_str::<Json>(&line) {
+            Ok(Json::Object(mut record)) => match record.remove("data") {
+                Some(Json::String(data)) => {
+                    let encoding = if is_hex(&data) { Encoding::Hex } else { Encoding::Base64 };
+                    match parse_input(data.into_bytes(), encoding)
+                        .map_err(DecodeError::input)
+                        .and_then(|bytes|decode_ibc(&bytes))
+                    {
+                        Ok(decoded) => record.insert("decoded".into(), decoded.to_json()),
+                        Err(error) => record.insert("error".into(), error.to_json()),
+                    };
+                    Json::Object(record)
+                },
+                _ => invalid_line(index, "record has no \"data\" string"),
+            },
+            Ok(_) => invalid_line(index, "record is not an object"),
+            Err(error) => invalid_line(index, format!("invalid JSON: {error}")),
+        };
+        serde_json::to_writer(&mut output, &record)?;
+        output.write_all(b"\n")?;
+    }
+    output.flush()
+}
+
+/// Error record for a line of the stream that isn't a valid record.
+fn invalid_line (index: usize, message: impl Into<String>) -> serde_json::Value {
+    serde_json::json!({ "line": index + 1, "error": DecodeError::input(message).to_json() })
+}
+
+fn parse_args () -> Result<Args, String> {
+    let mut result = Args::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--hex"    => result.encoding = Some(Encoding::Hex),
+            "--base64" => result.encoding = Some(Encoding::Base64),
+            "--raw"    => result.encoding = Some(Encoding::Raw),
+            "--file"   => result.file = Some(args.next().ok_or("--file requires a path")?),
+            "--ndjson" => result.ndjson = true,
+            "--validator-sets" => result.options.validator_sets = match args.next().as_deref() {
+                Some("full") => ValidatorSets::Full,
+                Some("hash") => ValidatorSets::Hash,
+                _ => return Err("--validator-sets requires \"full\" or \"hash\"".into()),
+            },
+            "--format" => result.options.format = match args.next().as_deref() {
+                Some("native") => Format::Native,
+                Some("proto3") => Format::Proto3,
+                _ => return Err("--format requires \"native\" or \"proto3\"".into()),
+            },
+            "--lenient" => result.options.lenient = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                std::process::exit(0)
+            },
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {arg}")),
+            _ if result.payload.is_some() => return Err(format!("Unexpected argument: {arg}")),
+            _ => result.payload = Some(arg),
+        }
+    }
+    Ok(result)
+}
+
+/// Read the payload that the arguments point to.
+fn read_input (Args { encoding, file, payload, .. }: Args) -> Result<Vec<u8>, String> {
+    let mut encoding = encoding.unwrap_or(Encoding::Auto);
+    let input = match (file, payload) {
+        (Some(_), Some(_)) => return Err("Pass either --file or a payload, not both".into()),
+        (Some(path), None) => std::fs::read(&path)
+            .map_err(|e|format!("Failed to read {path}: {e}"))?,
+        (None, Some(payload)) => {
+            if encoding == Encoding::Auto {
+                encoding = if is_hex(payload.trim()) { Encoding::Hex } else { Encoding::Base64 };
+            }
+            payload.into_bytes()
+        },
+        (None, None) => {
+            let mut input = vec![];
+            std::io::stdin().read_to_end(&mut input)
+                .map_err(|e|format!("Failed to read stdin: {e}"))?;
+            input
+        },
+    };
+    parse_input(input, encoding)
+}
+
+/// Decode the payload's text encoding, if any.
+fn parse_input (input: Vec<u8>, encoding: Encoding) -> Result<Vec<u8>, String> {
+    use base64::prelude::*;
+    let text = || std::str::from_utf8(&input)
+        .map(str::trim)
+        .map_err(|_|"Input is not valid text".to_string());
+    match encoding {
+        Encoding::Raw => Ok(input.clone()),
+        Encoding::Hex => hex::decode(strip_hex_prefix(text()?))
+            .map_err(|e|format!("Invalid hex: {e}")),
+        Encoding::Base64 => BASE64_STANDARD.decode(text()?)
+            .map_err(|e|format!("Invalid base64: {e}")),
+        Encoding::Auto => Ok(match text() {
+            Ok(text) if is_hex(text) => hex::decode(strip_hex_prefix(text)).unwrap(),
+            Ok(text) => BASE64_STANDARD.decode(text).unwrap_or_else(|_|input.clone()),
+            Err(_) => input.clone(),
+        }),
+    }
+}
+
+fn strip_hex_prefix (text: &str) -> &str {
+    text.strip_prefix("0x").unwrap_or(text)
+}
+
+fn is_hex (text: &str) -> bool {
+    let text = strip_hex_prefix(text);
+    !text.is_empty() && text.len() % 2 == 0 && text.bytes().all(|b|b.is_ascii_hexdigit())
+}
@@ -0,0 +1,466 @@
+/** A 64-bit integer: a `bigint`, or a decimal string when decoding with `{ integers: "string" }`. */
+export type Int64 = bigint | string;
+
+export interface DecodeOptions {
+  validatorSets?: "full" | "hash";
+  integers?: "bigint" | "string";
+  format?: "native" | "proto3";
+  lenient?: boolean;
+}
+
+/** A message in canonical proto3 JSON, as output with `{ format: "proto3" }`. */
+export interface Proto3Message {
+  "@type": string;
+  schemaVersion: number;
+  [field: string]: unknown;
+}
+
+export type Proto3Options = DecodeOptions & { format: "proto3" };
+
+/** The output of these depends on the `format` option, so they have an overload per format. */
+export declare namespace Decode {
+  function ibc (source: Uint8Array, options: Proto3Options): Proto3Message;
+  function ibc (source: Uint8Array, options?: DecodeOptions | null): DecodedIbc;
+  function ibcBatch (sources: unknown[], options: Proto3Options): DecodeBatchResult<Proto3Message>[];
+  function ibcBatch (sources: unknown[], options?: DecodeOptions | null): DecodeBatchResult[];
+  function tx (source: Uint8Array, options: Proto3Options): DecodedTxEntry<Proto3Message>[];
+  function tx (source: Uint8Array, options?: DecodeOptions | null): DecodedTxEntry[];
+}
+
+/**
+ * `value` is the output of the decoder registered for `typeUrl`, or the raw bytes if there is none,
+ * or a `DecodeFailure` if the decoder failed with `{ lenient: true }`.
+ * `raw` is present when there is a decoder, and is what `Encode.ibc` re-encodes.
+ */
+export interface Any {
+  typeUrl: string;
+  value: unknown;
+  raw?: Uint8Array;
+}
+
+export interface Height {
+  revisionNumber: Int64;
+  revisionHeight: Int64;
+}
+
+export interface Duration {
+  nanos: Int64;
+  iso: string;
+}
+
+export type TimeoutHeight = "never" | Height;
+
+export type TimeoutTimestamp = "never" | { nanos: Int64; iso: string };
+
+export type Order = "ORDER_NONE_UNSPECIFIED" | "ORDER_UNORDERED" | "ORDER_ORDERED";
+
+export interface ConnectionCounterparty {
+  clientId: string;
+  connectionId: string | null;
+  prefix: Uint8Array;
+}
+
+export interface ConnectionVersion {
+  identifier: string;
+  features: string[];
+}
+
+export interface Packet {
+  seqOnA: Int64;
+  portIdOnA: string;
+  chanIdOnA: string;
+  portIdOnB: string;
+  chanIdOnB: string;
+  data: Uint8Array;
+  packetData: PacketData | DecodeFailure;
+  timeoutHeightOnB: TimeoutHeight;
+  timeoutTimestampOnB: TimeoutTimestamp;
+}
+
+export type PacketData =
+  | { kind: "ics20"; denom: string; amount: string; token: PrefixedCoin; sender: string; receiver: string; memo: Memo }
+  | ({ kind: "ics721" } & NftPacketData)
+  | { kind: "text"; text: string }
+  | { kind: "bytes"; bytes: Uint8Array };
+
+export interface Acknowledgement {
+  status: "success" | "error" | "unknown";
+  result: Uint8Array | null;
+  error: string | null;
+  app: { kind: "ics20" | "ics721"; success: boolean } | null;
+  raw: Uint8Array;
+}
+
+/** `ibcDenom` and `namadaToken` are those of the token on the chain that processes the message, the receiving one for `envelope.packet.recv`. */
+export interface PrefixedCoin {
+  denom: string;
+  amount: string;
+  baseDenom: string;
+  trace: { portId: string; channelId: string | null }[];
+  ibcDenom: string | null;
+  namadaToken: string | null;
+}
+
+export type Memo =
+  | { kind: "empty" | "text"; text: string }
+  | { kind: "namada-shielding"; text: string; shielding: ShieldingData }
+  | JsonMemo;
+
+export interface ShieldingData {
+  maspTxId: string;
+  shieldedSectionHash: string;
+  spends: number;
+  converts: number;
+  outputs: number;
+  transparentInputs: number;
+  transparentOutputs: number;
+}
+
+export interface JsonMemo {
+  kind: "forward" | "wasm-hook" | "callback" | "json";
+  text: string;
+  json: Record<string, unknown>;
+  forward?: {
+    receiver: unknown;
+    port: unknown;
+    channel: unknown;
+    timeout: unknown;
+    retries: unknown;
+    next: Memo | null;
+  };
+  wasm?: { contract: unknown; msg: unknown };
+  srcCallback?: { address: unknown; gasLimit: unknown };
+  destCallback?: { address: unknown; gasLimit: unknown };
+}
+
+export interface TransferPacketData {
+  token: PrefixedCoin;
+  sender: string;
+  receiver: string;
+  memo: Memo;
+}
+
+export interface NftPacketData {
+  classId: { tracePath: string; baseClassId: string };
+  classUri: string | null;
+  classData: string | null;
+  tokenIds: string[];
+  tokenUris: string[] | null;
+  tokenData: string[] | null;
+  sender: string;
+  receiver: string;
+  memo: Memo | null;
+}
+
+export interface MsgTransfer {
+  portIdOnA: string;
+  chanIdOnA: string;
+  packetData: TransferPacketData;
+  timeoutHeightOnB: TimeoutHeight;
+  timeoutTimestampOnB: TimeoutTimestamp;
+}
+
+export interface MsgNftTransfer {
+  portIdOnA: string;
+  chanIdOnA: string;
+  packetData: NftPacketData;
+  timeoutHeightOnB: TimeoutHeight;
+  timeoutTimestampOnB: TimeoutTimestamp;
+}
+
+/** Amount in base units, as a decimal string, and its number of decimal places. */
+export interface DenominatedAmount {
+  amount: string;
+  denom: number;
+}
+
+export interface NamadaTransfer {
+  sources: { owner: string; token: string; amount: DenominatedAmount }[];
+  targets: { owner: string; token: string; amount: DenominatedAmount }[];
+  shieldedSectionHash: string | null;
+}
+
+/** An IBC inner transaction of a `Tx`; a failure to decode it doesn't fail the others. */
+export type DecodedTxEntry<T = DecodedIbc> = { index: number; codeHash: string; dataHash: string } & (
+  | { ok: true; decoded: T }
+  | { ok: false; error: IbcDecodeError }
+);
+
+/** Error thrown, or returned in batches, when a message can't be decoded. */
+export interface IbcDecodeError extends Error {
+  kind: "borsh" | "protobuf" | "unknown-type-url" | "invalid-identifier" | "conversion" | "invalid-input";
+  stage: "tx" | "envelope" | "any" | "packet-data" | "memo" | "input";
+  typeUrl: string | null;
+  path: string;
+  offset: number | null;
+}
+
+/** Output with `{ lenient: true }` in place of a nested value that failed to decode. */
+export interface DecodeFailure {
+  error: IbcDecodeError;
+  raw: Uint8Array;
+}
+
+export type DecodeBatchResult<T = DecodedIbc> =
+  | { ok: true; value: T }
+  | { ok: false; error: IbcDecodeError };
+
+export interface EnvelopeClientCreate {
+  type: "envelope.client.create";
+  schemaVersion: 6;
+  clientState: Any;
+  consensusState: Any;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeClientUpdate {
+  type: "envelope.client.update";
+  schemaVersion: 6;
+  clientId: string;
+  clientMessage: Any;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeClientMisbehaviour {
+  type: "envelope.client.misbehaviour";
+  schemaVersion: 6;
+  clientId: string;
+  misbehaviour: Any;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeClientUpgrade {
+  type: "envelope.client.upgrade";
+  schemaVersion: 6;
+  clientId: string;
+  upgradedClientState: Any;
+  upgradedConsensusState: Any;
+  proofUpgradeClient: Uint8Array;
+  proofUpgradeConsensusState: Uint8Array;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeClientRecover {
+  type: "envelope.client.recover";
+  schemaVersion: 6;
+  subjectClientId: string;
+  substituteClientId: string;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeConnectionOpenInit {
+  type: "envelope.connection.open_init";
+  schemaVersion: 6;
+  clientIdOnA: string;
+  counterparty: ConnectionCounterparty;
+  version: ConnectionVersion | null;
+  delayPeriod: Duration;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeConnectionOpenTry {
+  type: "envelope.connection.open_try";
+  schemaVersion: 6;
+  clientIdOnB: string;
+  clientStateOfBOnA: Any;
+  counterparty: ConnectionCounterparty;
+  versionsOnA: ConnectionVersion[];
+  proofConnEndOnA: Uint8Array;
+  proofClientStateOfBOnA: Uint8Array;
+  proofConsensusStateOfBOnA: Uint8Array;
+  proofsHeightOnA: Height;
+  consensusHeightOfBOnA: Height;
+  delayPeriod: Duration;
+  signer: string;
+  proofConsensusStateOfB: Uint8Array | null;
+  previousConnectionId: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeConnectionOpenAck {
+  type: "envelope.connection.open_ack";
+  schemaVersion: 6;
+  connIdOnA: string;
+  connIdOnB: string;
+  clientStateOfAOnB: Any;
+  proofConnEndOnB: Uint8Array;
+  proofClientStateOfAOnB: Uint8Array;
+  proofConsensusStateOfAOnB: Uint8Array;
+  proofsHeightOnB: Height;
+  consensusHeightOfAOnB: Height;
+  version: ConnectionVersion;
+  signer: string;
+  proofConsensusStateOfA: Uint8Array | null;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeConnectionOpenConfirm {
+  type: "envelope.connection.open_confirm";
+  schemaVersion: 6;
+  connIdOnB: string;
+  proofConnEndOnA: Uint8Array;
+  proofHeightOnA: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeChannelOpenInit {
+  type: "envelope.channel.open_init";
+  schemaVersion: 6;
+  portIdOnA: string;
+  connectionHopsOnA: string[];
+  portIdOnB: string;
+  ordering: Order;
+  signer: string;
+  versionProposal: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeChannelOpenTry {
+  type: "envelope.channel.open_try";
+  schemaVersion: 6;
+  portIdOnB: string;
+  connectionHopsOnB: string[];
+  portIdOnA: string;
+  chanIdOnA: string;
+  versionSupportedOnA: string;
+  proofChanEndOnA: Uint8Array;
+  proofHeightOnA: Height;
+  ordering: Order;
+  signer: string;
+  versionProposal: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeChannelOpenAck {
+  type: "envelope.channel.open_ack";
+  schemaVersion: 6;
+  portIdOnA: string;
+  chanIdOnA: string;
+  chanIdOnB: string;
+  versionOnB: string;
+  proofChanEndOnB: Uint8Array;
+  proofHeightOnB: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeChannelOpenConfirm {
+  type: "envelope.channel.open_confirm";
+  schemaVersion: 6;
+  portIdOnB: string;
+  chanIdOnB: string;
+  proofChanEndOnA: Uint8Array;
+  proofHeightOnA: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeChannelCloseInit {
+  type: "envelope.channel.close_init";
+  schemaVersion: 6;
+  portIdOnA: string;
+  chanIdOnA: string;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopeChannelCloseConfirm {
+  type: "envelope.channel.close_confirm";
+  schemaVersion: 6;
+  portIdOnB: string;
+  chanIdOnB: string;
+  proofChanEndOnA: Uint8Array;
+  proofHeightOnA: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopePacketRecv {
+  type: "envelope.packet.recv";
+  schemaVersion: 6;
+  packet: Packet;
+  proofCommitmentOnA: Uint8Array;
+  proofHeightOnA: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopePacketAck {
+  type: "envelope.packet.ack";
+  schemaVersion: 6;
+  packet: Packet;
+  acknowledgement: Acknowledgement;
+  proofAckedOnB: Uint8Array;
+  proofHeightOnB: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopePacketTimeout {
+  type: "envelope.packet.timeout";
+  schemaVersion: 6;
+  packet: Packet;
+  nextSeqRecvOnB: Int64;
+  proofUnreceivedOnB: Uint8Array;
+  proofHeightOnB: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface EnvelopePacketTimeoutOnClose {
+  type: "envelope.packet.timeout_on_close";
+  schemaVersion: 6;
+  packet: Packet;
+  nextSeqRecvOnB: Int64;
+  proofUnreceivedOnB: Uint8Array;
+  proofCloseOnB: Uint8Array;
+  proofHeightOnB: Height;
+  signer: string;
+  warnings?: IbcDecodeError[];
+}
+
+export interface Transfer {
+  type: "transfer";
+  schemaVersion: 6;
+  message: MsgTransfer;
+  transfer: NamadaTransfer | null;
+  warnings?: IbcDecodeError[];
+}
+
+export interface NftTransfer {
+  type: "nft_transfer";
+  schemaVersion: 6;
+  message: MsgNftTransfer;
+  transfer: NamadaTransfer | null;
+  warnings?: IbcDecodeError[];
+}
+
+export type DecodedIbc =
+  | EnvelopeClientCreate
+  | EnvelopeClientUpdate
+  | EnvelopeClientMisbehaviour
+  | EnvelopeClientUpgrade
+  | EnvelopeClientRecover
+  | EnvelopeConnectionOpenInit
+  | EnvelopeConnectionOpenTry
+  | EnvelopeConnectionOpenAck
+  | EnvelopeConnectionOpenConfirm
+  | EnvelopeChannelOpenInit
+  | EnvelopeChannelOpenTry
+  | EnvelopeChannelOpenAck
+  | EnvelopeChannelOpenConfirm
+  | EnvelopeChannelCloseInit
+  | EnvelopeChannelCloseConfirm
+  | EnvelopePacketRecv
+  | EnvelopePacketAck
+  | EnvelopePacketTimeout
+  | EnvelopePacketTimeoutOnClose
+  | Transfer
+  | NftTransfer;
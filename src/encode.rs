@@ -0,0 +1,636 @@
+//! Encoding of messages back to the bytes of an IBC transaction's data section.
+//!
+//! This is the inverse of `Decode.ibc`: [`from_value`] reads the structure that
+//! [`crate::ToJS`] outputs for a [`DecodedIbc`], and [`encode`] produces the bytes
+//! that `decode_message` accepts. IBC envelopes and plain transfers are encoded
+//! as protobuf `Any`s; transfers with an attached Namada transfer are borsh-encoded.
+//!
+//! Fields that are derived while decoding (such as `packetData`, or the `status`
+//! of an acknowledgement) are ignored; the raw fields they're derived from are used.
+//! Byte arrays can be passed either as `Uint8Array`s or as base64 strings, and 64-bit
+//! integers as `BigInt`s, numbers or decimal strings, so that the output of
+//! `ibcSerialize` can be encoded as well. `Any` values that were decoded by a
+//! registered decoder are re-encoded from their `raw` bytes.
+
+use std::time::Duration;
+use base64::prelude::*;
+use namada_sdk::ibc::{
+    MsgTransfer as IbcMsgTransfer,
+    MsgNftTransfer as IbcMsgNftTransfer,
+    apps::{transfer, nft_transfer},
+    core::{
+        client::{context::types::msgs::*, types::Height},
+        connection::types::{msgs::*, Counterparty, version::Version as ConnectionVersion},
+        channel::types::{
+            msgs::*,
+            Version as ChannelVersion,
+            acknowledgement::Acknowledgement,
+            channel::Order,
+            packet::Packet,
+            timeout::{TimeoutHeight, TimeoutTimestamp},
+        },
+        commitment_types::commitment::{CommitmentPrefix, CommitmentProofBytes},
+        host::types::identifiers::{ClientId, ConnectionId, ChannelId, PortId, Sequence},
+    },
+    primitives::{Signer, proto::Any},
+};
+use crate::{DecodedIbc, DecodeError, ErrorKind, Stage, Value, Object};
+
+/// Encode a message into the bytes of a data section.
+pub fn encode (decoded: &DecodedIbc) -> Result<Vec<u8>, DecodeError> {
+    use prost::Message;
+    use namada_sdk::{borsh::BorshSerializeExt, ibc::primitives::ToProto};
+    let any = match decoded.clone() {
+        DecodedIbc::Client(message) => match message {
+            ClientMsg::CreateClient(message)  => message.to_any(),
+            ClientMsg::UpdateClient(message)  => message.to_any(),
+            ClientMsg::Misbehaviour(message)  => message.to_any(),
+            ClientMsg::UpgradeClient(message) => message.to_any(),
+            ClientMsg::RecoverClient(message) => message.to_any(),
+        },
+        DecodedIbc::Connection(message) => match message {
+            ConnectionMsg::OpenInit(message)    => message.to_any(),
+            ConnectionMsg::OpenTry(message)     => message.to_any(),
+            ConnectionMsg::OpenAck(message)     => message.to_any(),
+            ConnectionMsg::OpenConfirm(message) => message.to_any(),
+        },
+        DecodedIbc::Channel(message) => match message {
+            ChannelMsg::OpenInit(message)     => message.to_any(),
+            ChannelMsg::OpenTry(message)      => message.to_any(),
+            ChannelMsg::OpenAck(message)      => message.to_any(),
+            ChannelMsg::OpenConfirm(message)  => message.to_any(),
+            ChannelMsg::CloseInit(message)    => message.to_any(),
+            ChannelMsg::CloseConfirm(message) => message.to_any(),
+        },
+        DecodedIbc::Packet(message) => match message {
+            PacketMsg::Recv(message)           => message.to_any(),
+            PacketMsg::Ack(message)            => message.to_any(),
+            PacketMsg::Timeout(message)        => message.to_any(),
+            PacketMsg::TimeoutOnClose(message) => message.to_any(),
+        },
+        DecodedIbc::Transfer(message) => match message.transfer {
+            None => message.message.to_any(),
+            Some(_) => return Ok(message.serialize_to_vec()),
+        },
+        DecodedIbc::NftTransfer(message) => match message.transfer {
+            None => message.message.to_any(),
+            Some(_) => return Ok(message.serialize_to_vec()),
+        },
+    };
+    Ok(any.encode_to_vec())
+}
+
+/// Read a message from the structure output by `Decode.ibc`.
+#[allow(deprecated)]
+pub fn from_value (value: &Value) -> Result<DecodedIbc, DecodeError> {
+    let object = Object::from_value(value)?;
+    let message_type: String = field(&object, "type")?;
+    let o = &object;
+    Ok(match message_type.as_str() {
+        "envelope.client.create" => DecodedIbc::Client(ClientMsg::CreateClient(MsgCreateClient {
+            client_state:    field(o, "clientState")?,
+            consensus_state: field(o, "consensusState")?,
+            signer:          field(o, "signer")?,
+        })),
+        "envelope.client.update" => DecodedIbc::Client(ClientMsg::UpdateClient(MsgUpdateClient {
+            client_id:      field(o, "clientId")?,
+            client_message: field(o, "clientMessage")?,
+            signer:         field(o, "signer")?,
+        })),
+        "envelope.client.misbehaviour" => DecodedIbc::Client(ClientMsg::Misbehaviour(MsgSubmitMisbehaviour {
+            client_id:    field(o, "clientId")?,
+            misbehaviour: field(o, "misbehaviour")?,
+            signer:       field(o, "signer")?,
+        })),
+        "envelope.client.upgrade" => DecodedIbc::Client(ClientMsg::UpgradeClient(MsgUpgradeClient {
+            client_id:                     field(o, "clientId")?,
+            upgraded_client_state:         field(o, "upgradedClientState")?,
+            upgraded_consensus_state:      field(o, "upgradedConsensusState")?,
+            proof_upgrade_client:          field(o, "proofUpgradeClient")?,
+            proof_upgrade_consensus_state: field(o, "proofUpgradeConsensusState")?,
+            signer:                        field(o, "signer")?,
+        })),
+        "envelope.client.recover" => DecodedIbc::Client(ClientMsg::RecoverClient(MsgRecoverClient {
+            subject_client_id:    field(o, "subjectClientId")?,
+            substitute_client_id: field(o, "substituteClientId")?,
+            signer:               field(o, "signer")?,
+        })),
+        "envelope.connection.open_init" => DecodedIbc::Connection(ConnectionMsg::OpenInit(MsgConnectionOpenInit {
+            client_id_on_a: field(o, "clientIdOnA")?,
+            counterparty:   field(o, "counterparty")?,
+            version:        field(o, "version")?,
+            delay_period:   field(o, "delayPeriod")?,
+            signer:         field(o, "signer")?,
+        })),
+        "envelope.connection.open_try" => DecodedIbc::Connection(ConnectionMsg::OpenTry(MsgConnectionOpenTry {
+            client_id_on_b:                  field(o, "clientIdOnB")?,
+            client_state_of_b_on_a:          field(o, "clientStateOfBOnA")?,
+            counterparty:                    field(o, "counterparty")?,
+            versions_on_a:                   field(o, "versionsOnA")?,
+            proof_conn_end_on_a:             field(o, "proofConnEndOnA")?,
+            proof_client_state_of_b_on_a:    field(o, "proofClientStateOfBOnA")?,
+            proof_consensus_state_of_b_on_a: field(o, "proofConsensusStateOfBOnA")?,
+            proofs_height_on_a:              field(o, "proofsHeightOnA")?,
+            consensus_height_of_b_on_a:      field(o, "consensusHeightOfBOnA")?,
+            delay_period:                    field(o, "delayPeriod")?,
+            signer:                          field(o, "signer")?,
+            proof_consensus_state_of_b:      field(o, "proofConsensusStateOfB")?,
+            previous_connection_id:          field(o, "previousConnectionId")?,
+        })),
+        "envelope.connection.open_ack" => DecodedIbc::Connection(ConnectionMsg::OpenAck(MsgConnectionOpenAck {
+            conn_id_on_a:                    field(o, "connIdOnA")?,
+            conn_id_on_b:                    field(o, "connIdOnB")?,
+            client_state_of_a_on_b:          field(o, "clientStateOfAOnB")?,
+            proof_conn_end_on_b:             field(o, "proofConnEndOnB")?,
+            proof_client_state_of_a_on_b:    field(o, "proofClientStateOfAOnB")?,
+            proof_consensus_state_of_a_on_b: field(o, "proofConsensusStateOfAOnB")?,
+            proofs_height_on_b:              field(o, "proofsHeightOnB")?,
+            consensus_height_of_a_on_b:      field(o, "consensusHeightOfAOnB")?,
+            version:                         field(o, "version")?,
+            signer:                          field(o, "signer")?,
+            proof_consensus_state_of_a:      field(o, "proofConsensusStateOfA")?,
+        })),
+        "envelope.connection.open_confirm" => DecodedIbc::Connection(ConnectionMsg::OpenConfirm(MsgConnectionOpenConfirm {
+            conn_id_on_b:        field(o, "connIdOnB")?,
+            proof_conn_end_on_a: field(o, "proofConnEndOnA")?,
+            proof_height_on_a:   field(o, "proofHeightOnA")?,
+            signer:              field(o, "signer")?,
+        })),
+        "envelope.channel.open_init" => DecodedIbc::Channel(ChannelMsg::OpenInit(MsgChannelOpenInit {
+            port_id_on_a:         field(o, "portIdOnA")?,
+            connection_hops_on_a: field(o, "connectionHopsOnA")?,
+            port_id_on_b:         field(o, "portIdOnB")?,
+            ordering:             field(o, "ordering")?,
+            signer:               field(o, "signer")?,
+            version_proposal:     field(o, "versionProposal")?,
+        })),
+        "envelope.channel.open_try" => DecodedIbc::Channel(ChannelMsg::OpenTry(MsgChannelOpenTry {
+            port_id_on_b:           field(o, "portIdOnB")?,
+            connection_hops_on_b:   field(o, "connectionHopsOnB")?,
+            port_id_on_a:           field(o, "portIdOnA")?,
+            chan_id_on_a:           field(o, "chanIdOnA")?,
+            version_supported_on_a: field(o, "versionSupportedOnA")?,
+            proof_chan_end_on_a:    field(o, "proofChanEndOnA")?,
+            proof_height_on_a:      field(o, "proofHeightOnA")?,
+            ordering:               field(o, "ordering")?,
+            signer:                 field(o, "signer")?,
+            version_proposal:       field(o, "versionProposal")?,
+        })),
+        "envelope.channel.open_ack" => DecodedIbc::Channel(ChannelMsg::OpenAck(MsgChannelOpenAck {
+            port_id_on_a:        field(o, "portIdOnA")?,
+            chan_id_on_a:        field(o, "chanIdOnA")?,
+            chan_id_on_b:        field(o, "chanIdOnB")?,
+            version_on_b:        field(o, "versionOnB")?,
+            proof_chan_end_on_b: field(o, "proofChanEndOnB")?,
+            proof_height_on_b:   field(o, "proofHeightOnB")?,
+            signer:              field(o, "signer")?,
+        })),
+        "envelope.channel.open_confirm" => DecodedIbc::Channel(ChannelMsg::OpenConfirm(MsgChannelOpenConfirm {
+            port_id_on_b:        field(o, "portIdOnB")?,
+            chan_id_on_b:        field(o, "chanIdOnB")?,
+            proof_chan_end_on_a: field(o, "proofChanEndOnA")?,
+            proof_height_on_a:   field(o, "proofHeightOnA")?,
+            signer:              field(o, "signer")?,
+        })),
+        "envelope.channel.close_init" => DecodedIbc::Channel(ChannelMsg::CloseInit(MsgChannelCloseInit {
+            port_id_on_a: field(o, "portIdOnA")?,
+            chan_id_on_a: field(o, "chanIdOnA")?,
+            signer:       field(o, "signer")?,
+        })),
+        "envelope.channel.close_confirm" => DecodedIbc::Channel(ChannelMsg::CloseConfirm(MsgChannelCloseConfirm {
+            port_id_on_b:        field(o, "portIdOnB")?,
+            chan_id_on_b:        field(o, "chanIdOnB")?,
+            proof_chan_end_on_a: field(o, "proofChanEndOnA")?,
+            proof_height_on_a:   field(o, "proofHeightOnA")?,
+            signer:              field(o, "signer")?,
+        })),
+        "envelope.packet.recv" => DecodedIbc::Packet(PacketMsg::Recv(MsgRecvPacket {
+            packet:                field(o, "packet")?,
+            proof_commitment_on_a: field(o, "proofCommitmentOnA")?,
+            proof_height_on_a:     field(o, "proofHeightOnA")?,
+            signer:                field(o, "signer")?,
+        })),
+        "envelope.packet.ack" => DecodedIbc::Packet(PacketMsg::Ack(MsgAcknowledgement {
+            packet:            field(o, "packet")?,
+            acknowledgement:   field(o, "acknowledgement")?,
+            proof_acked_on_b:  field(o, "proofAckedOnB")?,
+            proof_height_on_b: field(o, "proofHeightOnB")?,
+            signer:            field(o, "signer")?,
+        })),
+        "envelope.packet.timeout" => DecodedIbc::Packet(PacketMsg::Timeout(MsgTimeout {
+            packet:                field(o, "packet")?,
+            next_seq_recv_on_b:    field(o, "nextSeqRecvOnB")?,
+            proof_unreceived_on_b: field(o, "proofUnreceivedOnB")?,
+            proof_height_on_b:     field(o, "proofHeightOnB")?,
+            signer:                field(o, "signer")?,
+        })),
+        "envelope.packet.timeout_on_close" => DecodedIbc::Packet(PacketMsg::TimeoutOnClose(MsgTimeoutOnClose {
+            packet:                field(o, "packet")?,
+            next_seq_recv_on_b:    field(o, "nextSeqRecvOnB")?,
+            proof_unreceived_on_b: field(o, "proofUnreceivedOnB")?,
+            proof_close_on_b:      field(o, "proofCloseOnB")?,
+            proof_height_on_b:     field(o, "proofHeightOnB")?,
+            signer:                field(o, "signer")?,
+        })),
+        "transfer" => {
+            let message: Object = field(o, "message")?;
+            let m = &message;
+            DecodedIbc::Transfer(Box::new(IbcMsgTransfer {
+                message: transfer::types::msgs::transfer::MsgTransfer {
+                    port_id_on_a:           field(m, "portIdOnA")?,
+                    chan_id_on_a:           field(m, "chanIdOnA")?,
+                    packet_data:            field(m, "packetData")?,
+                    timeout_height_on_b:    field(m, "timeoutHeightOnB")?,
+                    timeout_timestamp_on_b: field(m, "timeoutTimestampOnB")?,
+                },
+                transfer: field(o, "transfer")?,
+            }))
+        },
+        "nft_transfer" => {
+            let message: Object = field(o, "message")?;
+            let m = &message;
+            DecodedIbc::NftTransfer(IbcMsgNftTransfer {
+                message: nft_transfer::types::msgs::transfer::MsgTransfer {
+                    port_id_on_a:           field(m, "portIdOnA")?,
+                    chan_id_on_a:           field(m, "chanIdOnA")?,
+                    packet_data:            field(m, "packetData")?,
+                    timeout_height_on_b:    field(m, "timeoutHeightOnB")?,
+                    timeout_timestamp_on_b: field(m, "timeoutTimestampOnB")?,
+                },
+                transfer: field(o, "transfer")?,
+            })
+        },
+        other => return Err(DecodeError::new(
+            ErrorKind::UnknownTypeUrl, Stage::Input, format!("unknown message type: {other}")
+        ).at("type")),
+    })
+}
+
+/// Inverse of [`crate::ToJS`].
+trait FromValue: Sized {
+    fn from_value (value: &Value) -> Result<Self, DecodeError>;
+}
+
+/// Read a field of an object. Missing fields are read as `undefined`,
+/// and errors are attributed to the field's key.
+fn field<T: FromValue> (object: &Object, key: &str) -> Result<T, DecodeError> {
+    T::from_value(object.get(key).unwrap_or(&Value::Undefined)).map_err(|e|e.at(key))
+}
+
+fn expected<T> (what: &str, value: &Value) -> Result<T, DecodeError> {
+    Err(DecodeError::input(format!("expected {what}, got {value:?}")))
+}
+
+fn conversion (error: impl std::fmt::Display) -> DecodeError {
+    DecodeError::conversion(Stage::Input, format!("{error}"))
+}
+
+impl FromValue for Object {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::Object(object) => Ok(object.clone()),
+            _ => expected("an object", value),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::String(value) => Ok(value.clone()),
+            _ => expected("a string", value),
+        }
+    }
+}
+
+impl FromValue for u64 {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let result = match value {
+            Value::BigInt(value) => u64::try_from(*value).ok(),
+            Value::Number(value) if value.fract() == 0.0 && *value >= 0.0 => Some(*value as u64),
+            Value::String(value) => value.parse().ok(),
+            _ => None,
+        };
+        result.map_or_else(||expected("a 64-bit unsigned integer", value), Ok)
+    }
+}
+
+impl FromValue for Vec<u8> {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::Bytes(value) => Ok(value.clone()),
+            Value::String(value) => BASE64_STANDARD.decode(value)
+                .map_err(|e|conversion(format!("invalid base64: {e}"))),
+            _ => expected("a Uint8Array or base64 string", value),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::Undefined | Value::Null => Ok(None),
+            _ => T::from_value(value).map(Some),
+        }
+    }
+}
+
+/// Byte arrays are read by the impl for `Vec<u8>`, so this one is only for
+/// the types that are listed here.
+macro_rules! from_array {
+    ($($type:ty),* $(,)?) => {
+        $(impl FromValue for Vec<$type> {
+            fn from_value (value: &Value) -> Result<Self, DecodeError> {
+                match value {
+                    Value::Array(items) => items.iter().enumerate()
+                        .map(|(index, item)|<$type>::from_value(item)
+                            .map_err(|e|e.at(&index.to_string())))
+                        .collect(),
+                    _ => expected("an array", value),
+                }
+            }
+        })*
+    }
+}
+
+from_array! {
+    String,
+    ConnectionId,
+    ConnectionVersion,
+    nft_transfer::types::TokenId,
+    nft_transfer::types::TokenUri,
+    nft_transfer::types::TokenData,
+    NamadaAccount,
+}
+
+/// Types that are output as their string representation, and parsed back from it.
+/// Parse errors are of the given kind.
+macro_rules! from_str {
+    ($kind:ident: $($type:ty),* $(,)?) => {
+        $(impl FromValue for $type {
+            fn from_value (value: &Value) -> Result<Self, DecodeError> {
+                String::from_value(value)?.parse().map_err(|e|DecodeError::new(
+                    ErrorKind::$kind, Stage::Input, format!("{e}")
+                ))
+            }
+        })*
+    }
+}
+
+from_str! {
+    InvalidIdentifier:
+    ClientId,
+    ConnectionId,
+    ChannelId,
+    PortId,
+}
+
+from_str! {
+    Conversion:
+    transfer::types::PrefixedDenom,
+    transfer::types::Amount,
+    nft_transfer::types::TokenId,
+    nft_transfer::types::TokenUri,
+    nft_transfer::types::TokenData,
+    nft_transfer::types::ClassUri,
+    nft_transfer::types::ClassData,
+    nft_transfer::types::PrefixedClassId,
+    namada_sdk::address::Address,
+    namada_sdk::hash::Hash,
+}
+
+/// Amounts are rebuilt from their amount in base units and their denomination,
+/// so that they re-encode to the same bytes.
+impl FromValue for namada_sdk::token::DenominatedAmount {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        use namada_sdk::{token::{Amount, Denomination}, uint::Uint};
+        let object = Object::from_value(value)?;
+        let amount: String = field(&object, "amount")?;
+        let amount = Uint::from_dec_str(&amount)
+            .map_err(|e|conversion(format!("invalid amount {amount}: {e:?}")).at("amount"))?;
+        let denom: u64 = field(&object, "denom")?;
+        let denom = u8::try_from(denom)
+            .map_err(|_|conversion(format!("invalid denomination {denom}")).at("denom"))?;
+        Ok(Self::new(Amount::from_uint(amount, 0).map_err(conversion)?, Denomination(denom)))
+    }
+}
+
+impl FromValue for Signer {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        String::from_value(value).map(Signer::from)
+    }
+}
+
+impl FromValue for ChannelVersion {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        String::from_value(value).map(ChannelVersion::new)
+    }
+}
+
+impl FromValue for Sequence {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        u64::from_value(value).map(Sequence::from)
+    }
+}
+
+impl FromValue for CommitmentProofBytes {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        CommitmentProofBytes::try_from(Vec::<u8>::from_value(value)?)
+            .map_err(conversion)
+    }
+}
+
+impl FromValue for CommitmentPrefix {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        CommitmentPrefix::try_from(Vec::<u8>::from_value(value)?)
+            .map_err(conversion)
+    }
+}
+
+/// Acknowledgements are read from their `raw` bytes.
+impl FromValue for Acknowledgement {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let raw: Vec<u8> = field(&Object::from_value(value)?, "raw")?;
+        Acknowledgement::try_from(raw).map_err(conversion)
+    }
+}
+
+impl FromValue for Any {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let object = Object::from_value(value)?;
+        let type_url: String = field(&object, "typeUrl")?;
+        // Decoded values are re-encoded from the bytes they were decoded from.
+        let value = if object.get("raw").is_some() {
+            field(&object, "raw")?
+        } else {
+            field(&object, "value")?
+        };
+        Ok(Any { type_url, value })
+    }
+}
+
+impl FromValue for Height {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let object = Object::from_value(value)?;
+        Height::new(field(&object, "revisionNumber")?, field(&object, "revisionHeight")?)
+            .map_err(conversion)
+    }
+}
+
+impl FromValue for Duration {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        field(&Object::from_value(value)?, "nanos").map(Duration::from_nanos)
+    }
+}
+
+impl FromValue for TimeoutHeight {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::String(never) if never == "never" => Ok(TimeoutHeight::Never),
+            _ => Height::from_value(value).map(TimeoutHeight::At),
+        }
+    }
+}
+
+impl FromValue for TimeoutTimestamp {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        match value {
+            Value::String(never) if never == "never" => Ok(TimeoutTimestamp::Never),
+            _ => field(&Object::from_value(value)?, "nanos").map(TimeoutTimestamp::from_nanoseconds),
+        }
+    }
+}
+
+impl FromValue for Order {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        [Order::None, Order::Unordered, Order::Ordered].into_iter()
+            .find(|order|Value::from(order.as_str()) == *value)
+            .map_or_else(||expected("a channel order", value), Ok)
+    }
+}
+
+impl FromValue for ConnectionVersion {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let object = Object::from_value(value)?;
+        let identifier: String = field(&object, "identifier")?;
+        let features: Vec<String> = field(&object, "features")?;
+        serde_json::from_value(serde_json::json!({ "identifier": identifier, "features": features }))
+            .map_err(conversion)
+    }
+}
+
+impl FromValue for Counterparty {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let object = Object::from_value(value)?;
+        Ok(Counterparty::new(
+            field(&object, "clientId")?,
+            field(&object, "connectionId")?,
+            field(&object, "prefix")?,
+        ))
+    }
+}
+
+/// Packets are read from their raw `data`; the decoded `packetData` is ignored.
+impl FromValue for Packet {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let o = &Object::from_value(value)?;
+        Ok(Packet {
+            seq_on_a:               field(o, "seqOnA")?,
+            port_id_on_a:           field(o, "portIdOnA")?,
+            chan_id_on_a:           field(o, "chanIdOnA")?,
+            port_id_on_b:           field(o, "portIdOnB")?,
+            chan_id_on_b:           field(o, "chanIdOnB")?,
+            data:                   field(o, "data")?,
+            timeout_height_on_b:    field(o, "timeoutHeightOnB")?,
+            timeout_timestamp_on_b: field(o, "timeoutTimestampOnB")?,
+        })
+    }
+}
+
+/// Tokens are read from their full `denom` and `amount`; the parsed trace is ignored.
+impl FromValue for transfer::types::PrefixedCoin {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let object = Object::from_value(value)?;
+        Ok(transfer::types::Coin {
+            denom:  field(&object, "denom")?,
+            amount: field(&object, "amount")?,
+        })
+    }
+}
+
+/// Memos are read from their `text`; the classification is ignored.
+impl FromValue for transfer::types::Memo {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        field(&Object::from_value(value)?, "text").map(|text: String|text.into())
+    }
+}
+
+impl FromValue for nft_transfer::types::Memo {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        field(&Object::from_value(value)?, "text").map(|text: String|text.into())
+    }
+}
+
+impl FromValue for transfer::types::packet::PacketData {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let o = &Object::from_value(value)?;
+        Ok(transfer::types::packet::PacketData {
+            token:    field(o, "token")?,
+            sender:   field(o, "sender")?,
+            receiver: field(o, "receiver")?,
+            memo:     field(o, "memo")?,
+        })
+    }
+}
+
+impl FromValue for nft_transfer::types::packet::PacketData {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let o = &Object::from_value(value)?;
+        let class_id = Object::from_value(o.get("classId").unwrap_or(&Value::Undefined))
+            .map_err(|e|e.at("classId"))?;
+        let trace_path: String = field(&class_id, "tracePath")?;
+        let base_class_id: String = field(&class_id, "baseClassId")?;
+        let class_id = if trace_path.is_empty() {
+            base_class_id
+        } else {
+            format!("{trace_path}/{base_class_id}")
+        };
+        Ok(nft_transfer::types::packet::PacketData {
+            class_id:   class_id.parse().map_err(|e|conversion(e).at("classId"))?,
+            class_uri:  field(o, "classUri")?,
+            class_data: field(o, "classData")?,
+            token_ids:  nft_transfer::types::TokenIds(field(o, "tokenIds")?),
+            token_uris: field(o, "tokenUris")?,
+            token_data: field(o, "tokenData")?,
+            sender:     field(o, "sender")?,
+            receiver:   field(o, "receiver")?,
+            memo:       field(o, "memo")?,
+        })
+    }
+}
+
+/// One entry of the `sources` or `targets` of a Namada transfer.
+struct NamadaAccount(namada_sdk::token::Account, namada_sdk::token::DenominatedAmount);
+
+impl FromValue for NamadaAccount {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let o = &Object::from_value(value)?;
+        Ok(NamadaAccount(
+            namada_sdk::token::Account { owner: field(o, "owner")?, token: field(o, "token")? },
+            field(o, "amount")?,
+        ))
+    }
+}
+
+impl FromValue for namada_sdk::token::Transfer {
+    fn from_value (value: &Value) -> Result<Self, DecodeError> {
+        let o = &Object::from_value(value)?;
+        let accounts = |key: &str| field::<Vec<NamadaAccount>>(o, key)
+            .map(|accounts|accounts.into_iter().map(|NamadaAccount(account, amount)|(account, amount)).collect());
+        Ok(namada_sdk::token::Transfer {
+            sources:               accounts("sources")?,
+            targets:               accounts("targets")?,
+            shielded_section_hash: field(o, "shieldedSectionHash")?,
+        })
+    }
+}
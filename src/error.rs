@@ -0,0 +1,209 @@
+//! Decode errors, structured so that failures can be grouped by cause.
+//!
+//! Besides its message, every error says what `kind` of failure it is, and at which
+//! `stage` of decoding it happened. Where known, it also carries the type URL of the
+//! `Any` being decoded, the path of the field that failed, and the byte offset
+//! at which borsh decoding stopped.
+
+use wasm_bindgen::prelude::*;
+
+/// Error returned when an IBC message can't be decoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    pub message:  String,
+    pub kind:     ErrorKind,
+    pub stage:    Stage,
+    pub type_url: Option<String>,
+    /// Keys of the fields that lead to the failure, outermost first.
+    pub path:     Vec<String>,
+    pub offset:   Option<usize>,
+}
+
+/// What went wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Invalid borsh encoding.
+    Borsh,
+    /// Invalid protobuf encoding.
+    Protobuf,
+    /// An `Any` with a type URL that can't be decoded.
+    UnknownTypeUrl,
+    /// A client, connection, channel or port identifier that isn't valid.
+    InvalidIdentifier,
+    /// Well-formed data that can't be converted to the expected type.
+    Conversion,
+    /// Invalid arguments or options.
+    InvalidInput,
+}
+
+/// Where it went wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// The Namada transaction containing the IBC message.
+    Tx,
+    /// The IBC message itself.
+    Envelope,
+    /// An `Any` nested in the message, such as a client state or header.
+    Any,
+    /// The data of a packet, parsed according to its port.
+    PacketData,
+    /// The memo of a transfer.
+    Memo,
+    /// The arguments, options, or message passed to the decoder or encoder.
+    Input,
+}
+
+impl ErrorKind {
+    pub fn as_str (&self) -> &'static str {
+        match self {
+            Self::Borsh             => "borsh",
+            Self::Protobuf          => "protobuf",
+            Self::UnknownTypeUrl    => "unknown-type-url",
+            Self::InvalidIdentifier => "invalid-identifier",
+            Self::Conversion        => "conversion",
+            Self::InvalidInput      => "invalid-input",
+        }
+    }
+
+    /// The kind of an error from a dependency, by the type of the error
+    /// or of one of its sources. Anything unrecognized is a conversion error.
+    pub fn of (error: &(dyn std::error::Error + 'static)) -> Self {
+        use namada_sdk::ibc::core::host::types::error::IdentifierError;
+        let mut error = Some(error);
+        while let Some(current) = error {
+            if current.is::<IdentifierError>() {
+                return Self::InvalidIdentifier
+            }
+            if current.is::<prost::DecodeError>() {
+                return Self::Protobuf
+            }
+            error = current.source();
+        }
+        Self::Conversion
+    }
+}
+
+impl Stage {
+    pub fn as_str (&self) -> &'static str {
+        match self {
+            Self::Tx         => "tx",
+            Self::Envelope   => "envelope",
+            Self::Any        => "any",
+            Self::PacketData => "packet-data",
+            Self::Memo       => "memo",
+            Self::Input      => "input",
+        }
+    }
+}
+
+impl DecodeError {
+    pub fn new (kind: ErrorKind, stage: Stage, message: impl Into<String>) -> Self {
+        Self { message: message.into(), kind, stage, type_url: None, path: vec![], offset: None }
+    }
+
+    /// Shorthand for errors converting well-formed data.
+    pub fn conversion (stage: Stage, message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Conversion, stage, message)
+    }
+
+    /// Shorthand for errors in the arguments or options.
+    pub fn input (message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidInput, Stage::Input, message)
+    }
+
+    pub fn with_type_url (mut self, type_url: &str) -> Self {
+        self.type_url = Some(type_url.to_string());
+        self
+    }
+
+    pub fn with_offset (mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Attribute the error to the given stage.
+    pub fn in_stage (mut self, stage: Stage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    /// Attribute the error to the decoding of an `Any` with the given type URL,
+    /// unless it's already attributed to an `Any` nested in that one.
+    pub fn in_any (self, type_url: &str) -> Self {
+        if self.type_url.is_some() {
+            self
+        } else {
+            self.in_stage(Stage::Any).with_type_url(type_url)
+        }
+    }
+
+    /// Add the key of the field containing the failure,
+    /// as the error propagates out of an object.
+    pub fn at (mut self, key: &str) -> Self {
+        self.path.insert(0, key.to_string());
+        self
+    }
+
+    /// The path of the failing field, such as `packet.packetData`.
+    pub fn path_string (&self) -> String {
+        self.path.join(".")
+    }
+
+    /// The message, prefixed with the path of the failing field, if any.
+    pub fn describe (&self) -> String {
+        if self.path.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{}: {}", self.path_string(), self.message)
+        }
+    }
+
+    /// Serialize the same way as the JS error.
+    pub fn to_json (&self) -> serde_json::Value {
+        serde_json::json!({
+            "message": self.describe(),
+            "kind":    self.kind.as_str(),
+            "stage":   self.stage.as_str(),
+            "typeUrl": self.type_url,
+            "path":    self.path_string(),
+            "offset":  self.offset,
+        })
+    }
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt (&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} error at {} stage", self.describe(), self.kind.as_str(), self.stage.as_str())?;
+        if let Some(type_url) = &self.type_url {
+            write!(f, ", type URL {type_url}")?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, ", byte offset {offset}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// The JS error has the described message, and the other fields as properties:
+/// `kind`, `stage`, `typeUrl`, `path`, and `offset`. All of them are enumerable,
+/// so that they survive `JSON.stringify`.
+impl From<DecodeError> for js_sys::Error {
+    fn from (error: DecodeError) -> Self {
+        let result = js_sys::Error::new(&error.describe());
+        let set = |key: &str, value: JsValue| {
+            js_sys::Reflect::set(&result, &key.into(), &value).unwrap_throw();
+        };
+        // The message set by the constructor isn't enumerable, and assigning
+        // to it keeps it that way, so it's replaced with a plain property.
+        js_sys::Reflect::delete_property(&result, &"message".into()).unwrap_throw();
+        set("message", error.describe().into());
+        set("kind",    error.kind.as_str().into());
+        set("stage",   error.stage.as_str().into());
+        set("typeUrl", error.type_url.as_deref().map_or(JsValue::NULL, JsValue::from));
+        set("path",    error.path_string().into());
+        set("offset",  error.offset.map_or(JsValue::NULL, |offset|JsValue::from(offset as u32)));
+        result
+    }
+}
@@ -0,0 +1,255 @@
+//! Model of the JavaScript values produced by the decoder.
+//!
+//! Decoded messages are built as [`Value`]s natively, and only become real JS objects
+//! at the `wasm-bindgen` boundary. This way, the CLI can print the very same structure
+//! as JSON, without a JS engine.
+
+use wasm_bindgen::prelude::*;
+use base64::prelude::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    BigInt(i128),
+    String(String),
+    Bytes(Vec<u8>),
+    Array(Vec<Value>),
+    Object(Object),
+    Error(Box<crate::DecodeError>),
+    /// A nested failure that was recovered from in lenient mode. It's output
+    /// like an error, but only these become `warnings`, see [`crate::lenient`].
+    Failure(Box<crate::DecodeError>),
+}
+
+/// JS object with ordered keys.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Object(Vec<(String, Value)>);
+
+impl Object {
+    pub fn new () -> Self {
+        Self::default()
+    }
+
+    /// Set a property. Like `Reflect.set`, this overwrites
+    /// an existing property in place, keeping its position.
+    pub fn set (&mut self, key: &str, value: Value) {
+        if let Some((_, old)) = self.0.iter_mut().find(|(k, _)|k == key) {
+            *old = value;
+        } else {
+            self.0.push((key.to_string(), value));
+        }
+    }
+
+    pub fn get (&self, key: &str) -> Option<&Value> {
+        self.0.iter().find(|(k, _)|k == key).map(|(_, v)|v)
+    }
+
+    pub fn iter (&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(k, v)|(k.as_str(), v))
+    }
+
+    pub fn iter_mut (&mut self) -> impl Iterator<Item = (&str, &mut Value)> {
+        self.0.iter_mut().map(|(k, v)|(k.as_str(), v))
+    }
+}
+
+impl From<bool> for Value {
+    fn from (value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from (value: i32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<u32> for Value {
+    fn from (value: u32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+/// Like `JsValue::from(u64)`, this produces a `BigInt`.
+impl From<u64> for Value {
+    fn from (value: u64) -> Self {
+        Self::BigInt(value.into())
+    }
+}
+
+/// Like `JsValue::from(i64)`, this produces a `BigInt`.
+impl From<i64> for Value {
+    fn from (value: i64) -> Self {
+        Self::BigInt(value.into())
+    }
+}
+
+impl From<&str> for Value {
+    fn from (value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from (value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from (value: &[u8]) -> Self {
+        Self::Bytes(value.to_vec())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from (value: Vec<Value>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl From<Object> for Value {
+    fn from (value: Object) -> Self {
+        Self::Object(value)
+    }
+}
+
+/// Integers that don't fit in a JS number are output like other 64-bit integers,
+/// according to the `integers` option in effect.
+impl From<&serde_json::Value> for Value {
+    fn from (value: &serde_json::Value) -> Self {
+        use serde_json::Value as Json;
+        const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+        match value {
+            Json::Null => Self::Null,
+            Json::Bool(value) => Self::Bool(*value),
+            Json::Number(value) => match value.as_i64().map(i128::from).or(value.as_u64().map(i128::from)) {
+                Some(value) if value.abs() > MAX_SAFE_INTEGER => crate::Options::current().integers.to_value(value),
+                _ => Self::Number(value.as_f64().unwrap_or(f64::NAN)),
+            },
+            Json::String(value) => Self::String(value.clone()),
+            Json::Array(items) => Self::Array(items.iter().map(Self::from).collect()),
+            Json::Object(entries) => {
+                let mut object = Object::new();
+                for (key, value) in entries {
+                    object.set(key, Self::from(value));
+                }
+                Self::Object(object)
+            },
+        }
+    }
+}
+
+impl From<Value> for JsValue {
+    fn from (value: Value) -> Self {
+        match value {
+            Value::Undefined     => JsValue::UNDEFINED,
+            Value::Null          => JsValue::NULL,
+            Value::Bool(value)   => JsValue::from(value),
+            Value::Number(value) => JsValue::from(value),
+            Value::BigInt(value) => if let Ok(value) = i64::try_from(value) {
+                JsValue::from(value)
+            } else if let Ok(value) = u64::try_from(value) {
+                JsValue::from(value)
+            } else {
+                JsValue::from(js_sys::BigInt::new(&value.to_string().into()).unwrap_throw())
+            },
+            Value::String(value) => JsValue::from(value),
+            Value::Bytes(value)  => JsValue::from(js_sys::Uint8Array::from(value.as_slice())),
+            Value::Array(items)  => JsValue::from(items.into_iter()
+                .map(JsValue::from)
+                .collect::<js_sys::Array>()),
+            Value::Object(object) => {
+                let result = js_sys::Object::new();
+                for (key, value) in object.0 {
+                    js_sys::Reflect::set(&result, &key.into(), &value.into()).unwrap_throw();
+                }
+                JsValue::from(result)
+            },
+            Value::Error(error) | Value::Failure(error) => JsValue::from(js_sys::Error::from(*error)),
+        }
+    }
+}
+
+impl Value {
+    /// Convert a value returned from JS, such as the result of a registered decoder.
+    pub fn from_js (value: &JsValue) -> Self {
+        if value.is_undefined() {
+            Self::Undefined
+        } else if value.is_null() {
+            Self::Null
+        } else if let Some(value) = value.as_bool() {
+            Self::Bool(value)
+        } else if let Some(value) = value.as_f64() {
+            Self::Number(value)
+        } else if let Some(value) = value.as_string() {
+            Self::String(value)
+        } else if value.is_bigint() {
+            let value = String::from(value.unchecked_ref::<js_sys::BigInt>()
+                .to_string(10)
+                .unwrap_throw());
+            value.parse().map(Self::BigInt).unwrap_or(Self::String(value))
+        } else if let Some(value) = value.dyn_ref::<js_sys::Uint8Array>() {
+            Self::Bytes(value.to_vec())
+        } else if let Some(value) = value.dyn_ref::<js_sys::Array>() {
+            Self::Array(value.iter().map(|item|Self::from_js(&item)).collect())
+        } else if let Some(value) = value.dyn_ref::<js_sys::Error>() {
+            Self::Error(Box::new(crate::DecodeError::conversion(
+                crate::Stage::Any, String::from(value.message())
+            )))
+        } else if value.is_object() {
+            let mut object = Object::new();
+            for entry in js_sys::Object::entries(value.unchecked_ref()).iter() {
+                let entry: js_sys::Array = entry.unchecked_into();
+                if let Some(key) = entry.get(0).as_string() {
+                    object.set(&key, Self::from_js(&entry.get(1)));
+                }
+            }
+            Self::Object(object)
+        } else {
+            Self::Undefined
+        }
+    }
+
+    /// Convert to JSON the same way `ibcSerialize` does on the JS side:
+    /// byte arrays become base64, bigints become strings, and undefined
+    /// properties are omitted.
+    pub fn to_json (&self) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match self {
+            Self::Undefined | Self::Null => Json::Null,
+            Self::Bool(value)   => Json::from(*value),
+            Self::Number(value) => if value.fract() == 0.0 && value.abs() < 2f64.powi(53) {
+                Json::from(*value as i64)
+            } else {
+                Json::from(*value)
+            },
+            Self::BigInt(value) => Json::from(value.to_string()),
+            Self::String(value) => Json::from(value.as_str()),
+            Self::Bytes(value)  => Json::from(BASE64_STANDARD.encode(value)),
+            Self::Array(items)  => Json::from(items.iter()
+                .map(Self::to_json)
+                .collect::<Vec<_>>()),
+            Self::Object(object) => Json::Object(object.iter()
+                .filter(|(_, value)|!matches!(value, Self::Undefined))
+                .map(|(key, value)|(key.to_string(), value.to_json()))
+                .collect()),
+            Self::Error(error) | Self::Failure(error) => error.to_json(),
+        }
+    }
+}
+
+/// Get the message of a thrown JS value.
+pub fn error_message (error: &JsValue) -> String {
+    if let Some(error) = error.dyn_ref::<js_sys::Error>() {
+        error.message().into()
+    } else if let Some(error) = error.as_string() {
+        error
+    } else {
+        format!("{error:?}")
+    }
+}
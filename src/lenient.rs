@@ -0,0 +1,54 @@
+//! Lenient decoding, in which a nested failure doesn't discard the whole message.
+//!
+//! With `{ lenient: true }`, a nested `Any` or packet data that fails to decode
+//! is output as `{ error, raw }` in place of its decoded value, where `raw` is
+//! its undecoded bytes. The message then carries a top-level `warnings` list with
+//! every such error, whose `path` locates the failing field from the top of the message.
+
+use crate::{DecodeError, Options, ToJS, Value, Object};
+
+/// In lenient mode, output a failure to decode the given bytes as `{ error, raw }`.
+/// Otherwise, pass it on.
+pub fn recover (result: Result<Value, DecodeError>, raw: &[u8]) -> Result<Value, DecodeError> {
+    match result {
+        Err(error) if Options::current().lenient => Ok(Value::from(to_object! {
+            "error" = Value::Failure(Box::new(error)),
+            "raw"   = Value::from(raw),
+        })),
+        result => result,
+    }
+}
+
+/// Collect the errors that [`recover`] left in a decoded message, and add them
+/// to it as `warnings`. Their paths are completed to start from the top of the message.
+/// Other errors in the message, such as ones returned by decoders registered from JS,
+/// are not warnings.
+pub fn add_warnings (message: &mut Value) {
+    let mut warnings = vec![];
+    collect(message, &mut vec![], &mut warnings);
+    if let Value::Object(message) = message {
+        message.set("warnings", Value::Array(warnings));
+    }
+}
+
+fn collect (value: &mut Value, path: &mut Vec<String>, warnings: &mut Vec<Value>) {
+    match value {
+        Value::Failure(error) => {
+            // The error is the `error` of `{ error, raw }`, which is in place of the failing field.
+            let field = &path[..path.len().saturating_sub(1)];
+            error.path = field.iter().chain(error.path.iter()).cloned().collect();
+            warnings.push(Value::Error(error.clone()));
+        },
+        Value::Array(items) => for (index, item) in items.iter_mut().enumerate() {
+            path.push(index.to_string());
+            collect(item, path, warnings);
+            path.pop();
+        },
+        Value::Object(object) => for (key, value) in object.iter_mut() {
+            path.push(key.to_string());
+            collect(value, path, warnings);
+            path.pop();
+        },
+        _ => {},
+    }
+}
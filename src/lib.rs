@@ -1,356 +1,716 @@
 extern crate wasm_bindgen;
 extern crate console_error_panic_hook;
 use wasm_bindgen::prelude::*;
-use js_sys::*;
+use js_sys::{Array, Error, Uint8Array};
 
 macro_rules! to_object {
     ($($id:literal = $val:expr),* $(,)?) => {{
-        let object = Object::new();
-        $(Reflect::set(&object, &$id.into(), &$val.to_js()?)?;)*
+        let mut object = Object::new();
+        $(object.set($id, $val.to_js().map_err(|e|e.at($id))?);)*
         object
     }}
 }
 
+pub mod js;
+pub use js::{Value, Object};
+
+pub mod error;
+pub use error::{DecodeError, ErrorKind, Stage};
+
+pub mod registry;
+
+pub mod options;
+pub use options::Options;
+
+pub mod memo;
+pub mod lenient;
+pub mod schema;
+pub mod proto3;
+pub mod encode;
+
+#[wasm_bindgen(typescript_custom_section)]
+const DECODED_TS: &str = include_str!("decoded.d.ts");
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "DecodedIbc")]
+    pub type DecodedIbcJs;
+    #[wasm_bindgen(typescript_type = "DecodedTxEntry[]")]
+    pub type DecodedTxJs;
+    #[wasm_bindgen(typescript_type = "DecodeBatchResult[]")]
+    pub type DecodeBatchJs;
+    #[wasm_bindgen(extends = js_sys::Object, typescript_type = "DecodeOptions")]
+    pub type DecodeOptionsJs;
+}
+
 #[wasm_bindgen]
 pub struct Decode;
 
+/// Tag of the code section of inner transactions that carry IBC messages.
+const IBC_TX_TAG: &str = "tx_ibc.wasm";
+
 #[wasm_bindgen]
 impl Decode {
-    #[wasm_bindgen] pub fn ibc (source: Uint8Array) -> Result<Object, Error> {
+    /// Decode the data section of an IBC transaction.
+    ///
+    /// The optional `options` object controls the output, e.g. `{ validatorSets: "hash" }`.
+    /// 64-bit integers are `BigInt`s unless `{ integers: "string" }` is passed;
+    /// token amounts are always decimal strings. With `{ format: "proto3" }`, the
+    /// message is output in canonical proto3 JSON instead, as ibc-go and cosmjs do.
+    /// With `{ lenient: true }`, a nested `Any` or packet data that fails to decode
+    /// is output as `{ error, raw }`, and listed in the message's `warnings`.
+    ///
+    /// This and the other methods whose output depends on the `format` are declared
+    /// in `decoded.d.ts`, with an overload per format.
+    #[wasm_bindgen(skip_typescript)] pub fn ibc (
+        source: Uint8Array, options: Option<DecodeOptionsJs>
+    ) -> Result<DecodedIbcJs, Error> {
         console_error_panic_hook::set_once();
+        let options = Options::from_js(options.as_deref())?;
+        let decoded = options.scope(||decode_ibc(&source.to_vec()))?;
+        Ok(JsValue::from(decoded).unchecked_into())
+    }
 
-        use namada_sdk::{
-            ibc::{
-                decode_message,
-                IbcMessage,
-                MsgTransfer    as IbcMsgTransfer,
-                MsgNftTransfer as IbcMsgNftTransfer,
-                core::{
-                    handler::types::msgs::MsgEnvelope,
-                    client::context::types::msgs::*,
-                    connection::types::msgs::*,
-                    channel::types::msgs::*
+    /// Decode many IBC data sections in one call.
+    ///
+    /// A payload that fails to decode doesn't abort the batch. Instead, each entry
+    /// of the returned array is either `{ ok: true, value }` or `{ ok: false, error }`,
+    /// in the same order as the input.
+    #[wasm_bindgen(js_name = "ibcBatch", skip_typescript)] pub fn ibc_batch (
+        sources: Array, options: Option<DecodeOptionsJs>
+    ) -> Result<DecodeBatchJs, Error> {
+        console_error_panic_hook::set_once();
+        let options = Options::from_js(options.as_deref())?;
+        let results = Array::new_with_length(sources.length());
+        for (index, source) in sources.iter().enumerate() {
+            let result = source.dyn_into::<Uint8Array>()
+                .map_err(|_|DecodeError::input(format!("batch item {index} is not a Uint8Array")))
+                .and_then(|source|options.clone().scope(||decode_ibc(&source.to_vec())));
+            results.set(index as u32, JsValue::from(Value::from(match result {
+                Ok(value) => to_object! {
+                    "ok"    = true,
+                    "value" = value,
                 },
-                apps::{
-                    transfer::types::msgs::transfer::MsgTransfer,
-                    nft_transfer::types::msgs::transfer::MsgTransfer as MsgNftTransfer,
+                Err(error) => to_object! {
+                    "ok"    = false,
+                    "error" = error,
                 },
+            })));
+        }
+        Ok(results.unchecked_into())
+    }
+
+    /// Register a function to decode `Any` values with the given type URL.
+    ///
+    /// The function is called with the value's bytes and its type URL, and what it
+    /// returns becomes the decoded `value`. If it throws, decoding fails with that error.
+    /// This replaces any decoder previously registered for the type URL, including
+    /// a built-in one.
+    #[wasm_bindgen(js_name = "registerTypeUrl")]
+    pub fn register_type_url (type_url: String, decoder: js_sys::Function) {
+        use namada_sdk::ibc::primitives::proto::Any;
+        registry::register(&type_url, std::rc::Rc::new(move |any: &Any| {
+            let bytes = JsValue::from(Value::from(any.value.as_slice()));
+            let type_url = JsValue::from(any.type_url.as_str());
+            decoder.call2(&JsValue::NULL, &bytes, &type_url)
+                .map(|value|Value::from_js(&value))
+                .map_err(|error|DecodeError::conversion(Stage::Any, js::error_message(&error))
+                    .with_type_url(&any.type_url))
+        }));
+    }
+
+    /// Remove the decoder for the given type URL. Returns whether there was one.
+    #[wasm_bindgen(js_name = "unregisterTypeUrl")]
+    pub fn unregister_type_url (type_url: String) -> bool {
+        registry::unregister(&type_url)
+    }
+
+    /// List the type URLs that have a decoder, built-in or registered.
+    #[wasm_bindgen(js_name = "typeUrls")]
+    pub fn type_urls () -> Array {
+        registry::type_urls().into_iter().map(JsValue::from).collect()
+    }
+
+    /// Decode every IBC message in a borsh-encoded Namada `Tx`.
+    ///
+    /// Inner transactions are found by walking the header's batch of commitments,
+    /// and resolving each one's code and data sections by hash, so this doesn't
+    /// depend on the order of the sections. Returns an array of
+    /// `{ index, codeHash, dataHash, ok: true, decoded }`, where `index` is the
+    /// position of the commitment in the batch, or `{ index, codeHash, dataHash,
+    /// ok: false, error }` for an inner transaction whose message failed to decode.
+    #[wasm_bindgen(skip_typescript)] pub fn tx (
+        source: Uint8Array, options: Option<DecodeOptionsJs>
+    ) -> Result<DecodedTxJs, Error> {
+        console_error_panic_hook::set_once();
+        let options = Options::from_js(options.as_deref())?;
+        let decoded = options.scope(||decode_tx(&source.to_vec()))?;
+        Ok(decoded.into_iter().map(JsValue::from).collect::<Array>().unchecked_into())
+    }
+
+    /// The hash that identifies a borsh-encoded Namada `Tx`, i.e. the hash of its
+    /// header, for finding a transaction among the ones in a block.
+    #[wasm_bindgen(js_name = "txHash")] pub fn tx_hash (source: Uint8Array) -> Result<String, Error> {
+        console_error_panic_hook::set_once();
+        Ok(tx_hash(&source.to_vec())?)
+    }
+}
+
+#[wasm_bindgen]
+pub struct Encode;
+
+#[wasm_bindgen]
+impl Encode {
+    /// Encode a message into the data section of an IBC transaction.
+    ///
+    /// This is the inverse of `Decode.ibc`: it accepts the structure that `Decode.ibc`
+    /// returns, or that structure after `ibcSerialize` and `JSON.parse`.
+    #[wasm_bindgen] pub fn ibc (message: DecodedIbcJs) -> Result<Uint8Array, Error> {
+        console_error_panic_hook::set_once();
+        let decoded = encode::from_value(&Value::from_js(&message))?;
+        Ok(Uint8Array::from(encode::encode(&decoded)?.as_slice()))
+    }
+}
+
+/// Decode the data section of an IBC transaction, and convert it to JS
+/// in the output format of the current [`Options`].
+pub fn decode_ibc (source: &[u8]) -> Result<Value, DecodeError> {
+    let decoded = decode(source)?;
+    let Options { format, lenient, .. } = Options::current();
+    match format {
+        options::Format::Native => {
+            let mut output = decoded.to_js()?;
+            if lenient {
+                lenient::add_warnings(&mut output);
+            }
+            Ok(output)
+        },
+        options::Format::Proto3 => Ok(Value::from(&proto3::to_json(&decoded)?)),
+    }
+}
+
+/// The hash of the header of a Namada transaction, as shown by explorers.
+pub fn tx_hash (source: &[u8]) -> Result<String, DecodeError> {
+    use namada_sdk::{borsh::BorshDeserialize, tx::Tx};
+    let tx = Tx::try_from_slice(source)
+        .map_err(|e|DecodeError::new(ErrorKind::Borsh, Stage::Tx, format!("{e}")))?;
+    Ok(tx.header_hash().to_string())
+}
+
+/// Decode every IBC message in a Namada transaction, and convert them to JS.
+///
+/// Only a transaction that can't be decoded at all is an error. An inner transaction
+/// whose message fails to decode gets an entry with the error, and the rest are
+/// still decoded.
+pub fn decode_tx (source: &[u8]) -> Result<Vec<Value>, DecodeError> {
+    use namada_sdk::{borsh::BorshDeserialize, tx::{Tx, Section}};
+    let tx = Tx::try_from_slice(source)
+        .map_err(|e|DecodeError::new(ErrorKind::Borsh, Stage::Tx, format!("{e}")))?;
+    let mut decoded = vec![];
+    for (index, commitments) in tx.commitments().iter().enumerate() {
+        let tag = match tx.get_section(&commitments.code_hash).as_deref() {
+            Some(Section::Code(code)) => code.tag.clone(),
+            _ => None,
+        };
+        if tag.as_deref() != Some(IBC_TX_TAG) {
+            continue
+        }
+        let result = tx.data(commitments)
+            .ok_or_else(||DecodeError::conversion(Stage::Tx, format!(
+                "missing data section {} of inner tx {index}", commitments.data_hash
+            )))
+            .and_then(|data|decode_ibc(&data));
+        let mut entry = to_object! {
+            "index"    = index as u32,
+            "codeHash" = commitments.code_hash,
+            "dataHash" = commitments.data_hash,
+        };
+        match result {
+            Ok(value) => {
+                entry.set("ok", Value::from(true));
+                entry.set("decoded", value);
+            },
+            Err(error) => {
+                entry.set("ok", Value::from(false));
+                entry.set("error", error.to_js()?);
+            },
+        }
+        decoded.push(Value::from(entry));
+    }
+    Ok(decoded)
+}
+
+use ibc_proto::ibc::{
+    core::{client::v1 as raw_client, connection::v1 as raw_connection, channel::v1 as raw_channel},
+    applications::{transfer::v1 as raw_transfer, nft_transfer::v1 as raw_nft_transfer},
+};
+use namada_sdk::ibc::{
+    apps::{
+        transfer::types::msgs::transfer,
+        nft_transfer::types::msgs::transfer as nft_transfer,
+    },
+    core::{
+        client::context::types::msgs as client,
+        connection::types::msgs as connection,
+        channel::types::msgs as channel,
+    },
+};
+use namada_sdk::ibc::{
+    decode_message,
+    IbcMessage,
+    MsgTransfer    as IbcMsgTransfer,
+    MsgNftTransfer as IbcMsgNftTransfer,
+    core::{
+        handler::types::msgs::MsgEnvelope,
+        client::context::types::msgs::ClientMsg,
+        connection::types::msgs::ConnectionMsg,
+        channel::types::msgs::{ChannelMsg, PacketMsg},
+    },
+};
+
+/// An IBC message, as found in the data section of a Namada transaction.
+#[derive(Clone, Debug)]
+pub enum DecodedIbc {
+    Client(ClientMsg),
+    Connection(ConnectionMsg),
+    Channel(ChannelMsg),
+    Packet(PacketMsg),
+    Transfer(Box<IbcMsgTransfer<namada_sdk::token::Transfer>>),
+    NftTransfer(IbcMsgNftTransfer<namada_sdk::token::Transfer>),
+}
+
+/// Decode the data section of an IBC transaction.
+pub fn decode (source: &[u8]) -> Result<DecodedIbc, DecodeError> {
+    let message = decode_message::<namada_sdk::token::Transfer>(source)
+        .map_err(|e|diagnose(source).unwrap_or_else(||DecodeError::conversion(Stage::Envelope, format!("{e}"))))?;
+    Ok(match message {
+        IbcMessage::Envelope(message) => match *message {
+            MsgEnvelope::Client(message)     => DecodedIbc::Client(message),
+            MsgEnvelope::Connection(message) => DecodedIbc::Connection(message),
+            MsgEnvelope::Channel(message)    => DecodedIbc::Channel(message),
+            MsgEnvelope::Packet(message)     => DecodedIbc::Packet(message),
+        },
+        IbcMessage::Transfer(message)    => DecodedIbc::Transfer(message),
+        IbcMessage::NftTransfer(message) => DecodedIbc::NftTransfer(message),
+    })
+}
+
+/// The messages that [`decode`] accepts as protobuf `Any`s: their type URLs,
+/// as defined by ibc-rs, and the protobuf and domain types they're decoded into.
+macro_rules! message_types {
+    ($($type_url:expr => $raw:ty, $domain:ty;)*) => {
+        /// Decode the value of an `Any` the way [`decode_message`] does, one step
+        /// at a time, to find out which step fails.
+        fn envelope_error (any: &namada_sdk::ibc::primitives::proto::Any) -> Option<DecodeError> {
+            match any.type_url.as_str() {
+                $(type_url if type_url == $type_url => message_error::<$raw, $domain>(&any.value),)*
+                type_url => Some(DecodeError::new(
+                    ErrorKind::UnknownTypeUrl, Stage::Envelope, format!("unknown message type URL {type_url}")
+                )),
+            }
+        }
+    }
+}
+
+message_types! {
+    client::CREATE_CLIENT_TYPE_URL         => raw_client::MsgCreateClient,               client::MsgCreateClient;
+    client::UPDATE_CLIENT_TYPE_URL         => raw_client::MsgUpdateClient,               client::MsgUpdateClient;
+    client::UPGRADE_CLIENT_TYPE_URL        => raw_client::MsgUpgradeClient,              client::MsgUpgradeClient;
+    client::SUBMIT_MISBEHAVIOUR_TYPE_URL   => raw_client::MsgSubmitMisbehaviour,         client::MsgSubmitMisbehaviour;
+    client::RECOVER_CLIENT_TYPE_URL        => raw_client::MsgRecoverClient,              client::MsgRecoverClient;
+    connection::CONN_OPEN_INIT_TYPE_URL    => raw_connection::MsgConnectionOpenInit,     connection::MsgConnectionOpenInit;
+    connection::CONN_OPEN_TRY_TYPE_URL     => raw_connection::MsgConnectionOpenTry,      connection::MsgConnectionOpenTry;
+    connection::CONN_OPEN_ACK_TYPE_URL     => raw_connection::MsgConnectionOpenAck,      connection::MsgConnectionOpenAck;
+    connection::CONN_OPEN_CONFIRM_TYPE_URL => raw_connection::MsgConnectionOpenConfirm,  connection::MsgConnectionOpenConfirm;
+    channel::CHAN_OPEN_INIT_TYPE_URL       => raw_channel::MsgChannelOpenInit,           channel::MsgChannelOpenInit;
+    channel::CHAN_OPEN_TRY_TYPE_URL        => raw_channel::MsgChannelOpenTry,            channel::MsgChannelOpenTry;
+    channel::CHAN_OPEN_ACK_TYPE_URL        => raw_channel::MsgChannelOpenAck,            channel::MsgChannelOpenAck;
+    channel::CHAN_OPEN_CONFIRM_TYPE_URL    => raw_channel::MsgChannelOpenConfirm,        channel::MsgChannelOpenConfirm;
+    channel::CHAN_CLOSE_INIT_TYPE_URL      => raw_channel::MsgChannelCloseInit,          channel::MsgChannelCloseInit;
+    channel::CHAN_CLOSE_CONFIRM_TYPE_URL   => raw_channel::MsgChannelCloseConfirm,       channel::MsgChannelCloseConfirm;
+    channel::RECV_PACKET_TYPE_URL          => raw_channel::MsgRecvPacket,                channel::MsgRecvPacket;
+    channel::ACKNOWLEDGEMENT_TYPE_URL      => raw_channel::MsgAcknowledgement,           channel::MsgAcknowledgement;
+    channel::TIMEOUT_TYPE_URL              => raw_channel::MsgTimeout,                   channel::MsgTimeout;
+    channel::TIMEOUT_ON_CLOSE_TYPE_URL     => raw_channel::MsgTimeoutOnClose,            channel::MsgTimeoutOnClose;
+    // ibc-rs keeps the type URLs of transfers private, so they're taken from ibc-proto.
+    <raw_transfer::MsgTransfer as prost::Name>::type_url()     => raw_transfer::MsgTransfer,     transfer::MsgTransfer;
+    <raw_nft_transfer::MsgTransfer as prost::Name>::type_url() => raw_nft_transfer::MsgTransfer, nft_transfer::MsgTransfer;
+}
+
+/// Decode a message in two steps, to tell invalid protobuf apart from
+/// valid protobuf that doesn't convert to the domain type.
+fn message_error<Raw, Domain> (value: &[u8]) -> Option<DecodeError>
+where
+    Raw: prost::Message + Default,
+    Domain: TryFrom<Raw>,
+    Domain::Error: std::error::Error + 'static,
+{
+    match Raw::decode(value) {
+        Ok(raw) => Domain::try_from(raw).err().map(|e|{
+            DecodeError::new(ErrorKind::of(&e), Stage::Envelope, format!("{e}"))
+        }),
+        Err(e) => Some(DecodeError::new(ErrorKind::Protobuf, Stage::Envelope, format!("{e}"))),
+    }
+}
+
+/// Find out why [`decode_message`] failed, by retracing its steps:
+/// it tries the source as a protobuf `Any`, then as a borsh-encoded transfer.
+fn diagnose (source: &[u8]) -> Option<DecodeError> {
+    use namada_sdk::ibc::primitives::proto::Any;
+    use prost::Message;
+    if let Ok(any) = Any::decode(source) {
+        if any.type_url.starts_with('/') {
+            return envelope_error(&any).map(|error|error.with_type_url(&any.type_url))
+        }
+    }
+    // Not an `Any`, so it should be a transfer with a Namada transfer attached.
+    // Report the error of whichever of the two message types got further.
+    [
+        borsh_error::<IbcMsgTransfer<namada_sdk::token::Transfer>>(source),
+        borsh_error::<IbcMsgNftTransfer<namada_sdk::token::Transfer>>(source),
+    ].into_iter().flatten().max_by_key(|error|error.offset)
+}
+
+/// Deserialize with borsh, keeping count of the bytes read, so that
+/// an error can say where decoding stopped.
+fn borsh_error<T: namada_sdk::borsh::BorshDeserialize> (source: &[u8]) -> Option<DecodeError> {
+    use std::io::Read;
+    struct Counting<'a> { source: &'a [u8], read: usize }
+    impl Read for Counting<'_> {
+        fn read (&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = (&self.source[self.read..]).read(buf)?;
+            self.read += read;
+            Ok(read)
+        }
+    }
+    let mut reader = Counting { source, read: 0 };
+    let error = match T::deserialize_reader(&mut reader) {
+        Err(e) => format!("{e}"),
+        Ok(_) if reader.read < source.len() => format!(
+            "{} unexpected bytes after the message", source.len() - reader.read
+        ),
+        Ok(_) => return None,
+    };
+    Some(DecodeError::new(ErrorKind::Borsh, Stage::Envelope, error).with_offset(reader.read))
+}
+
+impl ToJS for DecodedIbc {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        use namada_sdk::ibc::{
+            core::{
+                client::context::types::msgs::*,
+                connection::types::msgs::*,
+                channel::types::msgs::*
+            },
+            apps::{
+                transfer::types::msgs::transfer::MsgTransfer,
+                nft_transfer::types::msgs::transfer::MsgTransfer as MsgNftTransfer,
             },
-            token,
-            //systems::trans_token,
         };
-        let mut buffer = vec![0u8;source.length() as usize];
-        source.copy_to(buffer.as_mut_slice());
-        let message = decode_message::<()>(&buffer[..]).map_err(|e|Error::new(&format!("{e}")))?;
-        let decoded = match message {
-
-            IbcMessage::Envelope(message) => match *message {
-
-                MsgEnvelope::Client(message) => match message {
-                    ClientMsg::CreateClient(MsgCreateClient {
-                        client_state,
-                        consensus_state,
-                        signer,
-                    }) => to_object! {
-                        "type"           = "envelope.client.create",
-                        "clientState"    = client_state,
-                        "consensusState" = consensus_state,
-                        "signer"         = signer,
-                    },
-                    ClientMsg::UpdateClient(MsgUpdateClient {
-                        client_id,
-                        client_message,
-                        signer,
-                    }) => to_object! {
-                        "type"           = "envelope.client.update",
-                        "clientId"      = client_id,
-                        "clientMessage" = client_message,
-                        "signer"        = signer,
-                    },
-                    ClientMsg::Misbehaviour(MsgSubmitMisbehaviour {
-                        client_id,
-                        misbehaviour,
-                        signer,
-                    }) => to_object! {
-                        "type"           = "envelope.client.misbehaviour",
-                        "clientId"     = client_id,
-                        "misbehaviour" = misbehaviour,
-                        "signer"       = signer,
-                    },
-                    ClientMsg::UpgradeClient(MsgUpgradeClient {
-                        client_id,
-                        upgraded_client_state,
-                        upgraded_consensus_state,
-                        proof_upgrade_client,
-                        proof_upgrade_consensus_state,
-                        signer,
-                    }) => to_object! {
-                        "type"                       = "envelope.client.upgrade",
-                        "clientId"                   = client_id,
-                        "upgradedClientState"        = upgraded_client_state,
-                        "upgradedConsensusState"     = upgraded_consensus_state,
-                        "proofUpgradeClient"         = proof_upgrade_client,
-                        "proofUpgradeConsensusState" = proof_upgrade_consensus_state,
-                        "signer"                     = signer,
-                    },
-                    ClientMsg::RecoverClient(MsgRecoverClient {
-                        subject_client_id,
-                        substitute_client_id,
-                        signer,
-                    }) => to_object! {
-                        "type"               = "envelope.client.recover",
-                        "subjectClientId"    = subject_client_id,
-                        "substituteClientId" = substitute_client_id,
-                        "signer"             = signer,
-                    },
+        let message = match self {
+            DecodedIbc::Client(message) => match message {
+                ClientMsg::CreateClient(MsgCreateClient {
+                    client_state,
+                    consensus_state,
+                    signer,
+                }) => to_object! {
+                    "type"           = "envelope.client.create",
+                    "clientState"    = client_state,
+                    "consensusState" = consensus_state,
+                    "signer"         = signer,
                 },
-
-                MsgEnvelope::Connection(message) => match message {
-                    ConnectionMsg::OpenInit(MsgConnectionOpenInit {
-                        client_id_on_a,
-                        counterparty,
-                        version,
-                        delay_period,
-                        signer,
-                    }) => to_object! {
-                        "type"         = "envelope.connection.open_init",
-                        "clientIdOnA"  = client_id_on_a,
-                        "counterparty" = counterparty,
-                        "version"      = version,
-                        "delayPeriod"  = delay_period,
-                        "signer"       = signer,
-                    },
-                    ConnectionMsg::OpenTry(MsgConnectionOpenTry {
-                        client_id_on_b,
-                        client_state_of_b_on_a,
-                        counterparty,
-                        versions_on_a,
-                        proof_conn_end_on_a,
-                        proof_client_state_of_b_on_a,
-                        proof_consensus_state_of_b_on_a,
-                        proofs_height_on_a,
-                        consensus_height_of_b_on_a,
-                        delay_period,
-                        signer,
-                        proof_consensus_state_of_b,
-                        previous_connection_id,
-                    }) => to_object! {
-                        "type"                      = "envelope.connection.open_try",
-                        "clientIdOnB"               = client_id_on_b,
-                        "clientstateOfBOnA"         = client_state_of_b_on_a,
-                        "counterparty"              = counterparty,
-                        "versionsOnA"               = versions_on_a,
-                        "proofConnEndOnA"           = proof_conn_end_on_a,
-                        "proofClientStateOfBOnA"    = proof_client_state_of_b_on_a,
-                        "froofConsensusStateOfBOnA" = proof_consensus_state_of_b_on_a,
-                        "proofsHeightOnA"           = proofs_height_on_a,
-                        "consensusheightOfBOnA"     = consensus_height_of_b_on_a,
-                        "delayPeriod"               = delay_period,
-                        "signer"                    = signer,
-                        "proofConsensusStateOfB"    = proof_consensus_state_of_b,
-                        "previousConnectionId"      = previous_connection_id,
-                    },
-                    ConnectionMsg::OpenAck(MsgConnectionOpenAck {
-                        conn_id_on_a,
-                        conn_id_on_b,
-                        client_state_of_a_on_b,
-                        proof_conn_end_on_b,
-                        proof_client_state_of_a_on_b,
-                        proof_consensus_state_of_a_on_b,
-                        proofs_height_on_b,
-                        consensus_height_of_a_on_b,
-                        version,
-                        signer,
-                        proof_consensus_state_of_a,
-                    }) => to_object! {
-                        "type"                      = "envelope.connection.open_ack",
-                        "connIdOnA"                 = conn_id_on_a,
-                        "connIdOnB"                 = conn_id_on_b,
-                        "clientStateOfAOnB"         = client_state_of_a_on_b,
-                        "proofConnEndOnB"           = proof_conn_end_on_b,
-                        "proofClientStateOfAOnB"    = proof_client_state_of_a_on_b,
-                        "proofConsensusStateOfAOnB" = proof_consensus_state_of_a_on_b,
-                        "proofsHeightOnB"           = proofs_height_on_b,
-                        "consensusHeightOfAOnB"     = consensus_height_of_a_on_b,
-                        "version"                   = version,
-                        "signer"                    = signer,
-                        "proofConsensusStateOfA"    = proof_consensus_state_of_a,
-                    },
-                    ConnectionMsg::OpenConfirm(MsgConnectionOpenConfirm {
-                        conn_id_on_b,
-                        proof_conn_end_on_a,
-                        proof_height_on_a,
-                        signer,
-                    }) => to_object! {
-                        "type"            = "envelope.connection.open_confirm",
-                        "connIdOnB"       = conn_id_on_b,
-                        "proofConnEndOnA" = proof_conn_end_on_a,
-                        "proofHeightOnA"  = proof_height_on_a,
-                        "signer"          = signer,
-                    },
+                ClientMsg::UpdateClient(MsgUpdateClient {
+                    client_id,
+                    client_message,
+                    signer,
+                }) => to_object! {
+                    "type"           = "envelope.client.update",
+                    "clientId"      = client_id,
+                    "clientMessage" = client_message,
+                    "signer"        = signer,
+                },
+                ClientMsg::Misbehaviour(MsgSubmitMisbehaviour {
+                    client_id,
+                    misbehaviour,
+                    signer,
+                }) => to_object! {
+                    "type"           = "envelope.client.misbehaviour",
+                    "clientId"     = client_id,
+                    "misbehaviour" = misbehaviour,
+                    "signer"       = signer,
                 },
+                ClientMsg::UpgradeClient(MsgUpgradeClient {
+                    client_id,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
+                    proof_upgrade_client,
+                    proof_upgrade_consensus_state,
+                    signer,
+                }) => to_object! {
+                    "type"                       = "envelope.client.upgrade",
+                    "clientId"                   = client_id,
+                    "upgradedClientState"        = upgraded_client_state,
+                    "upgradedConsensusState"     = upgraded_consensus_state,
+                    "proofUpgradeClient"         = proof_upgrade_client,
+                    "proofUpgradeConsensusState" = proof_upgrade_consensus_state,
+                    "signer"                     = signer,
+                },
+                ClientMsg::RecoverClient(MsgRecoverClient {
+                    subject_client_id,
+                    substitute_client_id,
+                    signer,
+                }) => to_object! {
+                    "type"               = "envelope.client.recover",
+                    "subjectClientId"    = subject_client_id,
+                    "substituteClientId" = substitute_client_id,
+                    "signer"             = signer,
+                },
+            },
 
-                MsgEnvelope::Channel(message) => match message {
-                    ChannelMsg::OpenInit(MsgChannelOpenInit {
-                        port_id_on_a,
-                        connection_hops_on_a,
-                        port_id_on_b,
-                        ordering,
-                        signer,
-                        version_proposal,
-                    }) => to_object! {
-                        "type"              = "envelope.channel.open_init",
-                        "portIdOnA"         = port_id_on_a,
-                        "connectionHopsOnA" = connection_hops_on_a,
-                        "portIdOnB"         = port_id_on_b,
-                        "ordering"          = ordering,
-                        "signer"            = signer,
-                        "versionProposal"   = version_proposal,
-                    },
-                    ChannelMsg::OpenTry(MsgChannelOpenTry {
-                        port_id_on_b,
-                        connection_hops_on_b,
-                        port_id_on_a,
-                        chan_id_on_a,
-                        version_supported_on_a,
-                        proof_chan_end_on_a,
-                        proof_height_on_a,
-                        ordering,
-                        signer,
-                        version_proposal,
-                    }) => to_object! {
-                        "type"                = "envelope.channel.open_try",
-                        "portIdOnB"           = port_id_on_b,
-                        "connectionHopsOnB"   = connection_hops_on_b,
-                        "portIdOnA"           = port_id_on_a,
-                        "chanIdOnA"           = chan_id_on_a,
-                        "versionSupportedOnA" = version_supported_on_a,
-                        "proofChanEndOnA"     = proof_chan_end_on_a,
-                        "proofHeightOnA"      = proof_height_on_a,
-                        "ordering"            = ordering,
-                        "signer"              = signer,
-                        "versionProposal"     = version_proposal,
-                    },
-                    ChannelMsg::OpenAck(MsgChannelOpenAck {
-                        port_id_on_a,
-                        chan_id_on_a,
-                        chan_id_on_b,
-                        version_on_b,
-                        proof_chan_end_on_b,
-                        proof_height_on_b,
-                        signer,
-                    }) => to_object! {
-                        "type"            = "envelope.channel.open_ack",
-                        "portIdOnA"       = port_id_on_a,
-                        "chanIdOnB"       = chan_id_on_a,
-                        "chanIdOnB"       = chan_id_on_b,
-                        "versionOnB"      = version_on_b,
-                        "proofChanEndOnB" = proof_chan_end_on_b,
-                        "proofHeightOnB"  = proof_height_on_b,
-                        "signer"          = signer,
-                    },
-                    ChannelMsg::OpenConfirm(MsgChannelOpenConfirm {
-                        port_id_on_b,
-                        chan_id_on_b,
-                        proof_chan_end_on_a,
-                        proof_height_on_a,
-                        signer,
-                    }) => to_object! {
-                        "type"            = "envelope.channel.open_confirm",
-                        "portIdOnB"       = port_id_on_b,
-                        "chanIdOnB"       = chan_id_on_b,
-                        "proofChanEndOnA" = proof_chan_end_on_a,
-                        "proofHeightOnA"  = proof_height_on_a,
-                        "signer"          = signer,
-                    },
-                    ChannelMsg::CloseInit(MsgChannelCloseInit {
-                        port_id_on_a,
-                        chan_id_on_a,
-                        signer,
-                    }) => to_object! {
-                        "type"      = "envelope.channel.close_init",
-                        "portIdOnA" = port_id_on_a,
-                        "chanIdOnA" = chan_id_on_a,
-                        "signer"    = signer,
-                    },
-                    ChannelMsg::CloseConfirm(MsgChannelCloseConfirm {
-                        port_id_on_b,
-                        chan_id_on_b,
-                        proof_chan_end_on_a,
-                        proof_height_on_a,
-                        signer,
-                    }) => to_object! {
-                        "type"            = "envelope.channel.close_confirm",
-                        "portIdOnB"       = port_id_on_b,
-                        "chanIdOnB"       = chan_id_on_b,
-                        "proofChanEndOnA" = proof_chan_end_on_a,
-                        "proofHeightOnA"  = proof_height_on_a,
-                        "signer"          = signer,
-                    },
+            DecodedIbc::Connection(message) => match message {
+                ConnectionMsg::OpenInit(MsgConnectionOpenInit {
+                    client_id_on_a,
+                    counterparty,
+                    version,
+                    delay_period,
+                    signer,
+                }) => to_object! {
+                    "type"         = "envelope.connection.open_init",
+                    "clientIdOnA"  = client_id_on_a,
+                    "counterparty" = counterparty,
+                    "version"      = version,
+                    "delayPeriod"  = delay_period,
+                    "signer"       = signer,
+                },
+                ConnectionMsg::OpenTry(MsgConnectionOpenTry {
+                    client_id_on_b,
+                    client_state_of_b_on_a,
+                    counterparty,
+                    versions_on_a,
+                    proof_conn_end_on_a,
+                    proof_client_state_of_b_on_a,
+                    proof_consensus_state_of_b_on_a,
+                    proofs_height_on_a,
+                    consensus_height_of_b_on_a,
+                    delay_period,
+                    signer,
+                    proof_consensus_state_of_b,
+                    previous_connection_id,
+                }) => to_object! {
+                    "type"                      = "envelope.connection.open_try",
+                    "clientIdOnB"               = client_id_on_b,
+                    "clientStateOfBOnA"         = client_state_of_b_on_a,
+                    "counterparty"              = counterparty,
+                    "versionsOnA"               = versions_on_a,
+                    "proofConnEndOnA"           = proof_conn_end_on_a,
+                    "proofClientStateOfBOnA"    = proof_client_state_of_b_on_a,
+                    "proofConsensusStateOfBOnA" = proof_consensus_state_of_b_on_a,
+                    "proofsHeightOnA"           = proofs_height_on_a,
+                    "consensusHeightOfBOnA"     = consensus_height_of_b_on_a,
+                    "delayPeriod"               = delay_period,
+                    "signer"                    = signer,
+                    "proofConsensusStateOfB"    = proof_consensus_state_of_b,
+                    "previousConnectionId"      = previous_connection_id,
+                },
+                ConnectionMsg::OpenAck(MsgConnectionOpenAck {
+                    conn_id_on_a,
+                    conn_id_on_b,
+                    client_state_of_a_on_b,
+                    proof_conn_end_on_b,
+                    proof_client_state_of_a_on_b,
+                    proof_consensus_state_of_a_on_b,
+                    proofs_height_on_b,
+                    consensus_height_of_a_on_b,
+                    version,
+                    signer,
+                    proof_consensus_state_of_a,
+                }) => to_object! {
+                    "type"                      = "envelope.connection.open_ack",
+                    "connIdOnA"                 = conn_id_on_a,
+                    "connIdOnB"                 = conn_id_on_b,
+                    "clientStateOfAOnB"         = client_state_of_a_on_b,
+                    "proofConnEndOnB"           = proof_conn_end_on_b,
+                    "proofClientStateOfAOnB"    = proof_client_state_of_a_on_b,
+                    "proofConsensusStateOfAOnB" = proof_consensus_state_of_a_on_b,
+                    "proofsHeightOnB"           = proofs_height_on_b,
+                    "consensusHeightOfAOnB"     = consensus_height_of_a_on_b,
+                    "version"                   = version,
+                    "signer"                    = signer,
+                    "proofConsensusStateOfA"    = proof_consensus_state_of_a,
+                },
+                ConnectionMsg::OpenConfirm(MsgConnectionOpenConfirm {
+                    conn_id_on_b,
+                    proof_conn_end_on_a,
+                    proof_height_on_a,
+                    signer,
+                }) => to_object! {
+                    "type"            = "envelope.connection.open_confirm",
+                    "connIdOnB"       = conn_id_on_b,
+                    "proofConnEndOnA" = proof_conn_end_on_a,
+                    "proofHeightOnA"  = proof_height_on_a,
+                    "signer"          = signer,
                 },
+            },
 
-                MsgEnvelope::Packet(message) => match message {
-                    PacketMsg::Recv(MsgRecvPacket {
-                        packet,
-                        proof_commitment_on_a,
-                        proof_height_on_a,
-                        signer,
-                    }) => to_object! {
-                        "type"               = "envelope.packet.recv",
-                        "packet"             = packet,
-                        "proofCommitmentOnA" = proof_commitment_on_a,
-                        "proofHeightOnA"     = proof_height_on_a,
-                        "signer"             = signer,
-                    },
-                    PacketMsg::Ack(MsgAcknowledgement {
-                        packet,
-                        acknowledgement,
-                        proof_acked_on_b,
-                        proof_height_on_b,
-                        signer,
-                    }) => to_object! {
-                        "type"            = "envelope.packet.ack",
-                        "packet"          = packet,
-                        "acknowledgement" = acknowledgement,
-                        "proofAckedOnB"   = proof_acked_on_b,
-                        "proofHeightOnB"  = proof_height_on_b,
-                        "signer"          = signer,
-                    },
-                    PacketMsg::Timeout(MsgTimeout {
-                        packet,
-                        next_seq_recv_on_b,
-                        proof_unreceived_on_b,
-                        proof_height_on_b,
-                        signer,
-                    }) => to_object! {
-                        "type"               = "envelope.packet.timeout",
-                        "packet"             = packet,
-                        "nextSeqRecvOnB"     = next_seq_recv_on_b,
-                        "proofUnreceivedOnB" = proof_unreceived_on_b,
-                        "proofHeightOnB"     = proof_height_on_b,
-                        "signer"             = signer,
-                    },
-                    PacketMsg::TimeoutOnClose(MsgTimeoutOnClose {
-                        packet,
-                        next_seq_recv_on_b,
-                        proof_unreceived_on_b,
-                        proof_close_on_b,
-                        proof_height_on_b,
-                        signer,
-                    }) => to_object! {
-                        "type"               = "envelope.packet.timeout_on_close",
-                        "packet"             = packet,
-                        "nextSeqRecvOnB"     = next_seq_recv_on_b,
-                        "proofUnreceivedOnB" = proof_unreceived_on_b,
-                        "proofCloseOnB"      = proof_close_on_b,
-                        "proofHeightOnB"     = proof_height_on_b,
-                        "signer"             = signer,
-                    },
+            DecodedIbc::Channel(message) => match message {
+                ChannelMsg::OpenInit(MsgChannelOpenInit {
+                    port_id_on_a,
+                    connection_hops_on_a,
+                    port_id_on_b,
+                    ordering,
+                    signer,
+                    version_proposal,
+                }) => to_object! {
+                    "type"              = "envelope.channel.open_init",
+                    "portIdOnA"         = port_id_on_a,
+                    "connectionHopsOnA" = connection_hops_on_a,
+                    "portIdOnB"         = port_id_on_b,
+                    "ordering"          = ordering,
+                    "signer"            = signer,
+                    "versionProposal"   = version_proposal,
+                },
+                ChannelMsg::OpenTry(MsgChannelOpenTry {
+                    port_id_on_b,
+                    connection_hops_on_b,
+                    port_id_on_a,
+                    chan_id_on_a,
+                    version_supported_on_a,
+                    proof_chan_end_on_a,
+                    proof_height_on_a,
+                    ordering,
+                    signer,
+                    version_proposal,
+                }) => to_object! {
+                    "type"                = "envelope.channel.open_try",
+                    "portIdOnB"           = port_id_on_b,
+                    "connectionHopsOnB"   = connection_hops_on_b,
+                    "portIdOnA"           = port_id_on_a,
+                    "chanIdOnA"           = chan_id_on_a,
+                    "versionSupportedOnA" = version_supported_on_a,
+                    "proofChanEndOnA"     = proof_chan_end_on_a,
+                    "proofHeightOnA"      = proof_height_on_a,
+                    "ordering"            = ordering,
+                    "signer"              = signer,
+                    "versionProposal"     = version_proposal,
+                },
+                ChannelMsg::OpenAck(MsgChannelOpenAck {
+                    port_id_on_a,
+                    chan_id_on_a,
+                    chan_id_on_b,
+                    version_on_b,
+                    proof_chan_end_on_b,
+                    proof_height_on_b,
+                    signer,
+                }) => to_object! {
+                    "type"            = "envelope.channel.open_ack",
+                    "portIdOnA"       = port_id_on_a,
+                    "chanIdOnA"       = chan_id_on_a,
+                    "chanIdOnB"       = chan_id_on_b,
+                    "versionOnB"      = version_on_b,
+                    "proofChanEndOnB" = proof_chan_end_on_b,
+                    "proofHeightOnB"  = proof_height_on_b,
+                    "signer"          = signer,
+                },
+                ChannelMsg::OpenConfirm(MsgChannelOpenConfirm {
+                    port_id_on_b,
+                    chan_id_on_b,
+                    proof_chan_end_on_a,
+                    proof_height_on_a,
+                    signer,
+                }) => to_object! {
+                    "type"            = "envelope.channel.open_confirm",
+                    "portIdOnB"       = port_id_on_b,
+                    "chanIdOnB"       = chan_id_on_b,
+                    "proofChanEndOnA" = proof_chan_end_on_a,
+                    "proofHeightOnA"  = proof_height_on_a,
+                    "signer"          = signer,
+                },
+                ChannelMsg::CloseInit(MsgChannelCloseInit {
+                    port_id_on_a,
+                    chan_id_on_a,
+                    signer,
+                }) => to_object! {
+                    "type"      = "envelope.channel.close_init",
+                    "portIdOnA" = port_id_on_a,
+                    "chanIdOnA" = chan_id_on_a,
+                    "signer"    = signer,
+                },
+                ChannelMsg::CloseConfirm(MsgChannelCloseConfirm {
+                    port_id_on_b,
+                    chan_id_on_b,
+                    proof_chan_end_on_a,
+                    proof_height_on_a,
+                    signer,
+                }) => to_object! {
+                    "type"            = "envelope.channel.close_confirm",
+                    "portIdOnB"       = port_id_on_b,
+                    "chanIdOnB"       = chan_id_on_b,
+                    "proofChanEndOnA" = proof_chan_end_on_a,
+                    "proofHeightOnA"  = proof_height_on_a,
+                    "signer"          = signer,
                 },
+            },
 
+            DecodedIbc::Packet(message) => match message {
+                PacketMsg::Recv(MsgRecvPacket {
+                    packet,
+                    proof_commitment_on_a,
+                    proof_height_on_a,
+                    signer,
+                }) => to_object! {
+                    "type"               = "envelope.packet.recv",
+                    "packet"             = ReceivedPacket(packet),
+                    "proofCommitmentOnA" = proof_commitment_on_a,
+                    "proofHeightOnA"     = proof_height_on_a,
+                    "signer"             = signer,
+                },
+                PacketMsg::Ack(MsgAcknowledgement {
+                    packet,
+                    acknowledgement,
+                    proof_acked_on_b,
+                    proof_height_on_b,
+                    signer,
+                }) => to_object! {
+                    "type"            = "envelope.packet.ack",
+                    "packet"          = packet,
+                    "acknowledgement" = PortAcknowledgement(&packet.port_id_on_a, acknowledgement),
+                    "proofAckedOnB"   = proof_acked_on_b,
+                    "proofHeightOnB"  = proof_height_on_b,
+                    "signer"          = signer,
+                },
+                PacketMsg::Timeout(MsgTimeout {
+                    packet,
+                    next_seq_recv_on_b,
+                    proof_unreceived_on_b,
+                    proof_height_on_b,
+                    signer,
+                }) => to_object! {
+                    "type"               = "envelope.packet.timeout",
+                    "packet"             = packet,
+                    "nextSeqRecvOnB"     = next_seq_recv_on_b,
+                    "proofUnreceivedOnB" = proof_unreceived_on_b,
+                    "proofHeightOnB"     = proof_height_on_b,
+                    "signer"             = signer,
+                },
+                PacketMsg::TimeoutOnClose(MsgTimeoutOnClose {
+                    packet,
+                    next_seq_recv_on_b,
+                    proof_unreceived_on_b,
+                    proof_close_on_b,
+                    proof_height_on_b,
+                    signer,
+                }) => to_object! {
+                    "type"               = "envelope.packet.timeout_on_close",
+                    "packet"             = packet,
+                    "nextSeqRecvOnB"     = next_seq_recv_on_b,
+                    "proofUnreceivedOnB" = proof_unreceived_on_b,
+                    "proofCloseOnB"      = proof_close_on_b,
+                    "proofHeightOnB"     = proof_height_on_b,
+                    "signer"             = signer,
+                },
             },
 
-            IbcMessage::Transfer(boxed_message) => {
+            DecodedIbc::Transfer(message) => {
                 let IbcMsgTransfer {
                     message: MsgTransfer {
                         port_id_on_a,
@@ -360,7 +720,7 @@ impl Decode {
                         timeout_timestamp_on_b,
                     },
                     transfer,
-                } = *boxed_message;
+                } = message.as_ref();
                 to_object! {
                     "type"    = "transfer",
                     "message" = to_object! {
@@ -374,7 +734,7 @@ impl Decode {
                 }
             },
 
-            IbcMessage::NftTransfer(message) => {
+            DecodedIbc::NftTransfer(message) => {
                 let IbcMsgNftTransfer {
                     message: MsgNftTransfer {
                         port_id_on_a,
@@ -398,208 +758,411 @@ impl Decode {
                 }
             },
         };
-        Ok(decoded)
+        // Every message carries the version of the schema it conforms to, right after its type.
+        let mut object = Object::new();
+        for (key, value) in message.iter() {
+            object.set(key, value.clone());
+            if key == "type" {
+                object.set("schemaVersion", Value::from(schema::VERSION));
+            }
+        }
+        Ok(Value::from(object))
     }
 }
 
 pub trait ToJS {
-    fn to_js (&self) -> Result<JsValue, Error>;
+    fn to_js (&self) -> Result<Value, DecodeError>;
 }
 
 impl ToJS for () {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::UNDEFINED)
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::Undefined)
     }
 }
 
 impl ToJS for &str {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.to_string()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(*self))
     }
 }
 
 impl<T: ToJS> ToJS for Option<T> {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         if let Some(value) = self {
             value.to_js()
         } else {
-            Ok(JsValue::NULL)
+            Ok(Value::Null)
         }
     }
 }
 
 impl<T: ToJS> ToJS for Vec<T> {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        let array = Array::new();
-        for item in self.iter() {
-            array.push(&item.to_js()?);
-        }
-        Ok(JsValue::from(&array))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::Array(self.iter().map(ToJS::to_js).collect::<Result<_, _>>()?))
     }
 }
 
 impl ToJS for Vec<u8> {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        let array = Uint8Array::new_with_length(self.len() as u32);
-        array.copy_from(self.as_slice());
-        Ok(JsValue::from(array))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_slice()))
+    }
+}
+
+impl ToJS for bool {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(*self))
+    }
+}
+
+impl ToJS for i32 {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(*self))
     }
 }
 
 impl ToJS for u32 {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(*self))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(*self))
+    }
+}
+
+impl ToJS for i64 {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Options::current().integers.to_value((*self).into()))
     }
 }
 
 impl ToJS for u64 {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(*self))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Options::current().integers.to_value((*self).into()))
     }
 }
 
 impl ToJS for String {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
+/// Durations are output as `{ nanos, iso }`, where `iso` is an ISO-8601 duration
+/// in seconds, such as `PT86400S` or `PT0.5S`.
 impl ToJS for std::time::Duration {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self:?}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        let nanos = u64::try_from(self.as_nanos())
+            .map_err(|_|DecodeError::conversion(Stage::Envelope, format!("duration out of range: {self:?}")))?;
+        let iso = match self.subsec_nanos() {
+            0 => format!("PT{}S", self.as_secs()),
+            subsec => format!("PT{}.{}S", self.as_secs(), format!("{subsec:09}").trim_end_matches('0')),
+        };
+        Ok(Value::from(to_object! {
+            "nanos" = nanos,
+            "iso"   = iso,
+        }))
     }
 }
 
 impl ToJS for Object {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.clone()))
+    }
+}
+
+impl ToJS for Value {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(self.clone())
+    }
+}
+
+impl ToJS for DecodeError {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::Error(Box::new(self.clone())))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::commitment_types::commitment::CommitmentPrefix {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        let bytes = self.as_bytes();
-        let array = Uint8Array::new_with_length(bytes.len() as u32);
-        array.copy_from(bytes);
-        Ok(JsValue::from(array))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_bytes()))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::channel::types::acknowledgement::Acknowledgement {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        let bytes = self.as_bytes();
-        let array = Uint8Array::new_with_length(bytes.len() as u32);
-        array.copy_from(bytes);
-        Ok(JsValue::from(&array))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_bytes()))
+    }
+}
+
+/// Acknowledgement, interpreted according to the port of the acknowledged packet.
+///
+/// The standard ICS-04 envelope is either `{"result": "<base64>"}` or `{"error": "..."}`.
+/// Acknowledgements that aren't in that format have status `"unknown"`.
+pub struct PortAcknowledgement<'a>(
+    pub &'a namada_sdk::ibc::core::host::types::identifiers::PortId,
+    pub &'a namada_sdk::ibc::core::channel::types::acknowledgement::Acknowledgement,
+);
+
+impl ToJS for PortAcknowledgement<'_> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        use base64::prelude::*;
+        use namada_sdk::ibc::apps::{transfer, nft_transfer};
+        let Self(port_id, acknowledgement) = self;
+        let envelope: Option<serde_json::Value> = serde_json::from_slice(acknowledgement.as_bytes()).ok();
+        let field = |key: &str| envelope.as_ref()
+            .and_then(|envelope|envelope.get(key))
+            .and_then(|value|value.as_str());
+        let (status, result, error) = match (field("result"), field("error")) {
+            (Some(result), None) => ("success", BASE64_STANDARD.decode(result).ok(), None),
+            (None, Some(error))  => ("error", None, Some(error)),
+            _ => ("unknown", None, None),
+        };
+        // Both ICS-20 and ICS-721 acknowledge success with the single byte 0x01.
+        let app = match port_id.as_str() {
+            transfer::types::PORT_ID_STR => Some("ics20"),
+            nft_transfer::types::PORT_ID_STR => Some("ics721"),
+            _ => None,
+        }.map(|kind|Ok::<_, DecodeError>(to_object! {
+            "kind"    = kind,
+            "success" = result.as_deref() == Some(&[1u8][..]),
+        })).transpose()?;
+        Ok(Value::from(to_object! {
+            "status" = status,
+            "result" = result,
+            "error"  = error,
+            "app"    = app,
+            "raw"    = acknowledgement,
+        }))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::channel::types::channel::Order {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.as_str()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::channel::types::packet::Packet {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        packet_to_js(self, false)
+    }
+}
+
+/// The packet of a `MsgRecvPacket`, which the chain processing the message receives,
+/// rather than the one of an acknowledgement or timeout, which that chain sent.
+pub struct ReceivedPacket<'a>(pub &'a namada_sdk::ibc::core::channel::types::packet::Packet);
+
+impl ToJS for ReceivedPacket<'_> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        packet_to_js(self.0, true)
+    }
+}
+
+fn packet_to_js (
+    packet: &namada_sdk::ibc::core::channel::types::packet::Packet, received: bool
+) -> Result<Value, DecodeError> {
+    Ok(Value::from(to_object! {
+        "seqOnA"              = packet.seq_on_a,
+        "portIdOnA"           = packet.port_id_on_a,
+        "chanIdOnA"           = packet.chan_id_on_a,
+        "portIdOnB"           = packet.port_id_on_b,
+        "chanIdOnB"           = packet.chan_id_on_b,
+        "data"                = packet.data,
+        "packetData"          = PortPacketData { packet, received },
+        "timeoutHeightOnB"    = packet.timeout_height_on_b,
+        "timeoutTimestampOnB" = packet.timeout_timestamp_on_b,
+    }))
+}
+
+/// Packet data, interpreted according to the port that sent it.
+///
+/// ICS-20 and ICS-721 packet data are parsed from JSON. Data for other ports
+/// is output as text if it's valid UTF-8, or as bytes otherwise. In lenient mode,
+/// invalid packet data is output as `{ error, raw }`.
+///
+/// The `ibcDenom` and `namadaToken` of an ICS-20 token are those of the token on the
+/// chain that processes the message. When the packet is `received`, that's the
+/// destination of the packet, where the token is minted with the destination's
+/// port and channel prefixed to its denomination, or, if it returns to the chain
+/// it came from, unescrowed with the source's port and channel removed from it.
+pub struct PortPacketData<'a> {
+    pub packet:   &'a namada_sdk::ibc::core::channel::types::packet::Packet,
+    pub received: bool,
+}
+
+impl ToJS for PortPacketData<'_> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        lenient::recover(self.decode(), &self.packet.data)
+    }
+}
+
+impl PortPacketData<'_> {
+    fn decode (&self) -> Result<Value, DecodeError> {
+        use namada_sdk::ibc::apps::{transfer, nft_transfer};
+        let Self { packet, received } = self;
+        let (port_id, data) = (&packet.port_id_on_a, &packet.data);
+        Ok(Value::from(if port_id.as_str() == transfer::types::PORT_ID_STR {
+            let data: transfer::types::packet::PacketData = serde_json::from_slice(data)
+                .map_err(|e|DecodeError::conversion(Stage::PacketData, format!("invalid ICS-20 packet data: {e}")))?;
+            let token = if *received {
+                prefixed_coin(&data.token, &self.received_denom(&data.token.denom))?
+            } else {
+                data.token.to_js()?
+            };
+            to_object! {
+                "kind"     = "ics20",
+                "denom"    = data.token.denom.to_string(),
+                "amount"   = data.token.amount.to_string(),
+                "token"    = token,
+                "sender"   = data.sender,
+                "receiver" = data.receiver,
+                "memo"     = data.memo,
+            }
+        } else if port_id.as_str() == nft_transfer::types::PORT_ID_STR {
+            let data: nft_transfer::types::packet::PacketData = serde_json::from_slice(data)
+                .map_err(|e|DecodeError::conversion(Stage::PacketData, format!("invalid ICS-721 packet data: {e}")))?;
+            to_object! {
+                "kind"      = "ics721",
+                "classId"   = data.class_id,
+                "classUri"  = data.class_uri,
+                "classData" = data.class_data,
+                "tokenIds"  = data.token_ids,
+                "tokenUris" = data.token_uris,
+                "tokenData" = data.token_data,
+                "sender"    = data.sender,
+                "receiver"  = data.receiver,
+                "memo"      = data.memo,
+            }
+        } else if let Ok(text) = std::str::from_utf8(data) {
+            to_object! {
+                "kind" = "text",
+                "text" = text,
+            }
+        } else {
+            to_object! {
+                "kind"  = "bytes",
+                "bytes" = data.to_vec(),
+            }
+        }))
+    }
+
+    /// The denomination on the destination chain of a token received through the packet.
+    fn received_denom (
+        &self, denom: &namada_sdk::ibc::apps::transfer::types::PrefixedDenom
+    ) -> namada_sdk::ibc::apps::transfer::types::PrefixedDenom {
+        use namada_sdk::ibc::apps::transfer::types::{is_receiver_chain_source, TracePrefix};
+        let packet = self.packet;
+        let mut denom = denom.clone();
+        if is_receiver_chain_source(packet.port_id_on_a.clone(), packet.chan_id_on_a.clone(), &denom) {
+            denom.remove_trace_prefix(&TracePrefix::new(packet.port_id_on_a.clone(), packet.chan_id_on_a.clone()));
+        } else {
+            denom.add_trace_prefix(TracePrefix::new(packet.port_id_on_b.clone(), packet.chan_id_on_b.clone()));
+        }
+        denom
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::channel::types::timeout::TimeoutHeight {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         Ok(match self {
-            Self::Never => JsValue::from("never"),
-            Self::At(height) => JsValue::from(height.to_js()?)
+            Self::Never => Value::from("never"),
+            Self::At(height) => height.to_js()?
         })
     }
 }
 
+/// Timeout timestamps are output as `{ nanos, iso }`, where `iso` is an RFC 3339
+/// timestamp, or as `"never"`.
 impl ToJS for namada_sdk::ibc::core::channel::types::timeout::TimeoutTimestamp {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(match self {
+            Self::Never => Value::from("never"),
+            Self::At(timestamp) => {
+                let nanos = timestamp.nanoseconds();
+                let iso = namada_sdk::tendermint::Time::from_unix_timestamp(
+                    (nanos / 1_000_000_000) as i64,
+                    (nanos % 1_000_000_000) as u32,
+                ).map_err(|e|DecodeError::conversion(Stage::Envelope, format!("invalid timeout timestamp {nanos}: {e}")))?;
+                Value::from(to_object! {
+                    "nanos" = nanos,
+                    "iso"   = iso,
+                })
+            }
+        })
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::client::types::Height {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
-            "revision_number" = self.revision_number(),
-            "revision_height" = self.revision_height(),
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
+            "revisionNumber" = self.revision_number(),
+            "revisionHeight" = self.revision_height(),
         }))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::host::types::identifiers::ClientId {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.as_str()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::host::types::identifiers::ChannelId {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.as_str()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::host::types::identifiers::PortId {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.as_str()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::host::types::identifiers::ConnectionId {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.as_str()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::host::types::identifiers::Sequence {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.value()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        self.value().to_js()
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::channel::types::Version {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.as_str()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
 impl ToJS for namada_sdk::ibc::primitives::Signer {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         //use namada_sdk::borsh::BorshSerializeExt;
         //let bytes = self.serialize_to_vec();
-        //let array = Uint8Array::new_with_length(bytes.len() as u32);
-        //array.copy_from(bytes.as_slice());
-        Ok(JsValue::from(self.as_ref().to_string()))
+        Ok(Value::from(self.as_ref().to_string()))
     }
 }
 
+/// Proofs are output as their raw bytes, as they are in the protobuf message.
 impl ToJS for namada_sdk::ibc::core::commitment_types::commitment::CommitmentProofBytes {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        use namada_sdk::borsh::BorshSerializeExt;
-        let bytes = self.serialize_to_vec();
-        let array = Uint8Array::new_with_length(bytes.len() as u32);
-        array.copy_from(bytes.as_slice());
-        Ok(JsValue::from(array))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::Bytes(Vec::<u8>::from(self.clone())))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::connection::types::version::Version {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        use namada_sdk::borsh::BorshSerialize;
-        let mut bytes: Vec<u8> = vec![];
-        self.serialize(&mut bytes.as_mut_slice()).map_err(|e|Error::new(&format!("{e}")))?;
-        let array = Uint8Array::new_with_length(bytes.len() as u32);
-        array.copy_from(bytes.as_slice());
-        Ok(JsValue::from(array))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        let version = serde_json::to_value(self)
+            .map_err(|e|DecodeError::conversion(Stage::Envelope, format!("{e}")))?;
+        Ok(Value::from(to_object! {
+            "identifier" = Value::from(&version["identifier"]),
+            "features"   = Value::from(&version["features"]),
+        }))
     }
 }
 
 impl ToJS for namada_sdk::ibc::core::connection::types::Counterparty {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "clientId"     = self.client_id,
             "connectionId" = self.connection_id,
             "prefix"       = self.prefix,
@@ -608,8 +1171,8 @@ impl ToJS for namada_sdk::ibc::core::connection::types::Counterparty {
 }
 
 impl ToJS for namada_sdk::ibc::apps::transfer::types::packet::PacketData {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "token"    = self.token,
             "sender"   = self.sender,
             "receiver" = self.receiver,
@@ -618,21 +1181,65 @@ impl ToJS for namada_sdk::ibc::apps::transfer::types::packet::PacketData {
     }
 }
 
-impl<D: std::fmt::Display> ToJS for namada_sdk::ibc::apps::transfer::types::Coin<D> {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+/// The token of an ICS-20 transfer, with its denomination trace split into hops.
+///
+/// For tokens that have crossed at least one channel, `ibcDenom` is the `ibc/<SHA256>`
+/// denomination that Cosmos chains use, and `namadaToken` is the address of the Namada
+/// IBC token with this trace. Tokens with no trace are native to the sending chain;
+/// for these, `namadaToken` is only set if the base denomination is a Namada address.
+impl ToJS for namada_sdk::ibc::apps::transfer::types::PrefixedCoin {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        prefixed_coin(self, &self.denom)
     }
 }
 
+/// A token, with the `ibcDenom` and `namadaToken` of the given denomination,
+/// which is that of the token on the chain that processes the message.
+fn prefixed_coin (
+    coin:        &namada_sdk::ibc::apps::transfer::types::PrefixedCoin,
+    local_denom: &namada_sdk::ibc::apps::transfer::types::PrefixedDenom,
+) -> Result<Value, DecodeError> {
+    use sha2::{Sha256, Digest};
+    let denom = coin.denom.to_string();
+    let base_denom = coin.denom.base_denom.to_string();
+    let trace_path = coin.denom.trace_path.to_string();
+    let segments: Vec<&str> = trace_path.split('/').filter(|s|!s.is_empty()).collect();
+    let mut trace = vec![];
+    for hop in segments.chunks(2) {
+        trace.push(Value::from(to_object! {
+            "portId"    = hop[0],
+            "channelId" = hop.get(1).copied(),
+        }));
+    }
+    let (ibc_denom, namada_token) = if local_denom.trace_path.is_empty() {
+        let base_denom = local_denom.base_denom.to_string();
+        (None, base_denom.parse::<namada_sdk::address::Address>().ok().map(|a|a.to_string()))
+    } else {
+        let local_denom = local_denom.to_string();
+        (
+            Some(format!("ibc/{}", hex::encode_upper(Sha256::digest(local_denom.as_bytes())))),
+            Some(namada_sdk::ibc::trace::ibc_token(&local_denom).to_string()),
+        )
+    };
+    Ok(Value::from(to_object! {
+        "denom"       = denom,
+        "amount"      = coin.amount.to_string(),
+        "baseDenom"   = base_denom,
+        "trace"       = trace,
+        "ibcDenom"    = ibc_denom,
+        "namadaToken" = namada_token,
+    }))
+}
+
 impl ToJS for namada_sdk::ibc::apps::transfer::types::Memo {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        memo::Memo(&self.to_string()).to_js().map_err(|e|e.in_stage(Stage::Memo))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::packet::PacketData {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "classId"   = self.class_id,
             "classUri"  = self.class_uri,
             "classData" = self.class_data,
@@ -647,56 +1254,56 @@ impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::packet::PacketData {
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::Memo {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        memo::Memo(&self.to_string()).to_js().map_err(|e|e.in_stage(Stage::Memo))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::TokenId {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::TokenIds {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         self.0.to_js()
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::TokenUri {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::TokenData {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::ClassId {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::ClassUri {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::ClassData {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
     }
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::PrefixedClassId {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "tracePath"   = self.trace_path,
             "baseClassId" = self.base_class_id,
         }))
@@ -704,35 +1311,34 @@ impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::PrefixedClassId {
 }
 
 impl ToJS for namada_sdk::ibc::apps::nft_transfer::types::TracePath {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(&format!("{self}")))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
     }
 }
 
 impl ToJS for namada_sdk::ibc::primitives::proto::Any {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        use namada_sdk::ibc::primitives::proto::Protobuf;
-        use namada_sdk::ibc::clients::tendermint::types::Header;
-        Ok(JsValue::from(/*match self.type_url.as_str() {
-            "/ibc.lightclients.tendermint.v1.Header" => {
-                let value: Header = Protobuf::<Self>::decode(self.value.as_slice())
-                    .map_err(|e|Error::new(&format!("{e}")))?;
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(match registry::get(&self.type_url) {
+            Some(decoder) => {
+                let value = decoder(self).map_err(|e|e.in_any(&self.type_url));
+                // The bytes are kept, so that the message can be re-encoded.
                 to_object! {
                     "typeUrl" = self.type_url,
-                    "value"   = value,
+                    "value"   = lenient::recover(value, &self.value)?,
+                    "raw"     = self.value,
                 }
             },
-            _ => */to_object! {
+            None => to_object! {
                 "typeUrl" = self.type_url,
                 "value"   = self.value,
-            }/*
-        }*/))
+            }
+        }))
     }
 }
 
 impl ToJS for namada_sdk::ibc::clients::tendermint::types::Header {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "signedHeader"            = self.signed_header,
             "validatorSet"            = self.validator_set,
             "trustedHeight"           = self.trusted_height,
@@ -741,15 +1347,115 @@ impl ToJS for namada_sdk::ibc::clients::tendermint::types::Header {
     }
 }
 
+impl ToJS for namada_sdk::ibc::clients::tendermint::types::Misbehaviour {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        let header1 = &self.header1().signed_header.header;
+        let header2 = &self.header2().signed_header.header;
+        // Two headers for the same height must be the same block, and
+        // a header for a later height can't have an earlier (or equal) time.
+        let kind = if header1.height == header2.height {
+            if header1.hash() != header2.hash() { "conflicting-block-hash" } else { "none" }
+        } else {
+            let (earlier, later) = if header1.height < header2.height {
+                (header1, header2)
+            } else {
+                (header2, header1)
+            };
+            if later.time <= earlier.time { "non-monotonic-time" } else { "none" }
+        };
+        Ok(Value::from(to_object! {
+            "clientId" = self.client_id(),
+            "header1"  = self.header1(),
+            "header2"  = self.header2(),
+            "conflict" = to_object! {
+                "kind"    = kind,
+                "height1" = header1.height,
+                "height2" = header2.height,
+                "hash1"   = header1.hash(),
+                "hash2"   = header2.hash(),
+                "time1"   = header1.time,
+                "time2"   = header2.time,
+            },
+        }))
+    }
+}
+
+impl ToJS for namada_sdk::ibc::clients::tendermint::types::ClientState {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
+            "chainId"         = self.chain_id,
+            "trustLevel"      = to_object! {
+                "numerator"   = self.trust_level.numerator(),
+                "denominator" = self.trust_level.denominator(),
+            },
+            "trustingPeriod"  = self.trusting_period,
+            "unbondingPeriod" = self.unbonding_period,
+            "maxClockDrift"   = self.max_clock_drift,
+            "latestHeight"    = self.latest_height,
+            "frozenHeight"    = self.frozen_height,
+            "proofSpecs"      = self.proof_specs,
+            "upgradePath"     = self.upgrade_path,
+        }))
+    }
+}
+
+impl ToJS for namada_sdk::ibc::clients::tendermint::types::ConsensusState {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
+            "timestamp"          = self.timestamp,
+            "root"               = self.root,
+            "nextValidatorsHash" = self.next_validators_hash,
+        }))
+    }
+}
+
+impl ToJS for namada_sdk::ibc::core::host::types::identifiers::ChainId {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
+    }
+}
+
+impl ToJS for namada_sdk::ibc::core::commitment_types::commitment::CommitmentRoot {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_bytes()))
+    }
+}
+
+impl ToJS for namada_sdk::ibc::core::commitment_types::specs::ProofSpecs {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        let specs: Vec<_> = Vec::from(self.clone());
+        Ok(Value::Array(specs.iter().map(|spec|Ok::<_, DecodeError>(Value::from(to_object! {
+            "leafSpec" = spec.leaf_spec.as_ref().map(|leaf|Ok::<_, DecodeError>(to_object! {
+                "hash"         = leaf.hash().as_str_name(),
+                "prehashKey"   = leaf.prehash_key().as_str_name(),
+                "prehashValue" = leaf.prehash_value().as_str_name(),
+                "length"       = leaf.length().as_str_name(),
+                "prefix"       = leaf.prefix,
+            })).transpose()?,
+            "innerSpec" = spec.inner_spec.as_ref().map(|inner|Ok::<_, DecodeError>(to_object! {
+                "childOrder"      = inner.child_order,
+                "childSize"       = inner.child_size,
+                "minPrefixLength" = inner.min_prefix_length,
+                "maxPrefixLength" = inner.max_prefix_length,
+                "emptyChild"      = inner.empty_child,
+                "hash"            = inner.hash().as_str_name(),
+            })).transpose()?,
+            "maxDepth"                   = spec.max_depth,
+            "minDepth"                   = spec.min_depth,
+            "prehashKeyBeforeComparison" = spec.prehash_key_before_comparison,
+        }))).collect::<Result<_, DecodeError>>()?))
+    }
+}
+
 impl ToJS for namada_sdk::tendermint::Signature {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         Vec::from(self.as_bytes()).to_js()
     }
 }
 
 impl ToJS for namada_sdk::tendermint::block::signed_header::SignedHeader {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "header" = self.header,
             "commit" = self.commit,
         }))
@@ -757,8 +1463,8 @@ impl ToJS for namada_sdk::tendermint::block::signed_header::SignedHeader {
 }
 
 impl ToJS for namada_sdk::tendermint::block::Header {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "version"            = self.version,
             "chainId"            = self.chain_id,
             "height"             = self.height,
@@ -778,8 +1484,8 @@ impl ToJS for namada_sdk::tendermint::block::Header {
 }
 
 impl ToJS for namada_sdk::tendermint::block::Id {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "hash"          = self.hash,
             "partSetHeader" = self.part_set_header,
         }))
@@ -787,14 +1493,14 @@ impl ToJS for namada_sdk::tendermint::block::Id {
 }
 
 impl ToJS for namada_sdk::tendermint::block::Height {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.value()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        self.value().to_js()
     }
 }
 
 impl ToJS for namada_sdk::tendermint::block::Commit {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "height"     = self.height,
             "round"      = self.round,
             "blockId"    = self.block_id,
@@ -804,14 +1510,14 @@ impl ToJS for namada_sdk::tendermint::block::Commit {
 }
 
 impl ToJS for namada_sdk::tendermint::block::Round {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         self.value().to_js()
     }
 }
 
 impl ToJS for namada_sdk::tendermint::block::commit_sig::CommitSig {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(match self {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(match self {
             Self::BlockIdFlagAbsent => to_object! {
                 "blockIdFlag" = "absent",
             },
@@ -840,8 +1546,8 @@ impl ToJS for namada_sdk::tendermint::block::commit_sig::CommitSig {
 }
 
 impl ToJS for namada_sdk::tendermint::block::header::Version {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "block" = self.block,
             "app"   = self.app,
         }))
@@ -849,8 +1555,8 @@ impl ToJS for namada_sdk::tendermint::block::header::Version {
 }
 
 impl ToJS for namada_sdk::tendermint::block::parts::Header {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
             "total" = self.total,
             "hash"  = self.hash,
         }))
@@ -858,39 +1564,120 @@ impl ToJS for namada_sdk::tendermint::block::parts::Header {
 }
 
 impl ToJS for namada_sdk::tendermint::chain::Id {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.as_str()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.as_str()))
     }
 }
 
 impl ToJS for namada_sdk::tendermint::account::Id {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         Vec::from(self.as_bytes()).to_js()
     }
 }
 
 impl ToJS for namada_sdk::tendermint::validator::Set {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(to_object! {
-            "hash" = self.hash()
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(match Options::current().validator_sets {
+            options::ValidatorSets::Hash => to_object! {
+                "hash" = self.hash(),
+            },
+            options::ValidatorSets::Full => to_object! {
+                "hash"             = self.hash(),
+                "validators"       = self.validators(),
+                "proposer"         = self.proposer(),
+                "totalVotingPower" = self.total_voting_power(),
+            },
+        }))
+    }
+}
+
+impl ToJS for namada_sdk::tendermint::validator::Info {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
+            "address"          = self.address,
+            "pubKey"           = self.pub_key,
+            "power"            = self.power,
+            "name"             = self.name,
+            "proposerPriority" = self.proposer_priority.value(),
+        }))
+    }
+}
+
+impl ToJS for namada_sdk::tendermint::PublicKey {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        // The amino type name (e.g. "tendermint/PubKeyEd25519") is only exposed through serde.
+        let json = serde_json::to_value(self)
+            .map_err(|e|DecodeError::conversion(Stage::Any, format!("{e}")))?;
+        Ok(Value::from(to_object! {
+            "type"  = json.get("type").and_then(|t|t.as_str()),
+            "bytes" = self.to_bytes(),
         }))
     }
 }
 
+impl ToJS for namada_sdk::tendermint::vote::Power {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        self.value().to_js()
+    }
+}
+
 impl ToJS for namada_sdk::tendermint::Hash {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         Vec::from(self.as_bytes()).to_js()
     }
 }
 
 impl ToJS for namada_sdk::tendermint::AppHash {
-    fn to_js (&self) -> Result<JsValue, Error> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
         Vec::from(self.as_bytes()).to_js()
     }
 }
 
+impl ToJS for namada_sdk::token::Transfer {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        use std::collections::BTreeMap;
+        use namada_sdk::token::{Account, DenominatedAmount};
+        fn accounts (accounts: &BTreeMap<Account, DenominatedAmount>) -> Result<Vec<Object>, DecodeError> {
+            accounts.iter().map(|(account, amount)|Ok::<_, DecodeError>(to_object! {
+                "owner"  = account.owner,
+                "token"  = account.token,
+                "amount" = amount,
+            })).collect()
+        }
+        Ok(Value::from(to_object! {
+            "sources"             = accounts(&self.sources)?,
+            "targets"             = accounts(&self.targets)?,
+            "shieldedSectionHash" = self.shielded_section_hash,
+        }))
+    }
+}
+
+impl ToJS for namada_sdk::address::Address {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.to_string()))
+    }
+}
+
+/// Amounts are output as `{ amount, denom }`, where `amount` is the integer amount
+/// in base units, as a decimal string, and `denom` is the number of decimal places.
+/// Their display form trims trailing zeros, so neither can be recovered from it.
+impl ToJS for namada_sdk::token::DenominatedAmount {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(to_object! {
+            "amount" = self.amount().raw_amount().to_string(),
+            "denom"  = u32::from(self.denom().0),
+        }))
+    }
+}
+
+impl ToJS for namada_sdk::hash::Hash {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(format!("{self}")))
+    }
+}
+
 impl ToJS for namada_sdk::tendermint::Time {
-    fn to_js (&self) -> Result<JsValue, Error> {
-        Ok(JsValue::from(self.to_rfc3339()))
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        Ok(Value::from(self.to_rfc3339()))
     }
 }
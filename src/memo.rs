@@ -0,0 +1,148 @@
+//! Classification of ICS-20 and ICS-721 memos.
+//!
+//! Every memo is output as `{ kind, text, ... }`, where `text` is the memo as sent,
+//! and `kind` tells which of the known formats it's in:
+//!
+//! * `empty`, `text`: no memo, or free text.
+//! * `namada-shielding`: shielding data for an inbound transfer to Namada.
+//! * `forward`: packet-forward-middleware, `{"forward": {...}}`.
+//! * `wasm-hook`: IBC hooks contract call, `{"wasm": {"contract": ..., "msg": ...}}`.
+//! * `callback`: ADR-8 callbacks, `{"src_callback": ...}` and/or `{"dest_callback": ...}`.
+//! * `json`: any other JSON object.
+//!
+//! JSON memos also carry the parsed `json`, and the recognized parts as typed objects.
+
+use serde_json::{Map, Value as Json};
+use crate::{ToJS, Value, Object, DecodeError, Options};
+
+/// A memo, as found in the packet data.
+pub struct Memo<'a>(pub &'a str);
+
+impl ToJS for Memo<'_> {
+    fn to_js (&self) -> Result<Value, DecodeError> {
+        let Self(text) = self;
+        Ok(Value::from(if text.is_empty() {
+            to_object! {
+                "kind" = "empty",
+                "text" = text,
+            }
+        } else if let Some(shielding) = shielding(text)? {
+            to_object! {
+                "kind"      = "namada-shielding",
+                "text"      = text,
+                "shielding" = shielding,
+            }
+        } else if let Ok(Json::Object(json)) = serde_json::from_str(text) {
+            json_memo(text, &json)?
+        } else {
+            to_object! {
+                "kind" = "text",
+                "text" = text,
+            }
+        }))
+    }
+}
+
+/// Decode the Namada shielding data that inbound shielded transfers carry in the memo.
+///
+/// The shielding data is a hex-encoded MASP transaction; its ID is also the hash
+/// of the MASP section in the Namada transaction that it ends up in.
+fn shielding (text: &str) -> Result<Option<Object>, DecodeError> {
+    use namada_sdk::{ibc::IbcShieldingData, hash::Hash};
+    let Ok(IbcShieldingData(masp_tx)) = text.parse::<IbcShieldingData>() else {
+        return Ok(None)
+    };
+    let txid = masp_tx.txid();
+    let sapling = masp_tx.sapling_bundle();
+    let transparent = masp_tx.transparent_bundle();
+    Ok(Some(to_object! {
+        "maspTxId"            = txid.to_string(),
+        "shieldedSectionHash" = Hash(*txid.as_ref()),
+        "spends"              = sapling.map_or(0, |b|b.shielded_spends.len() as u32),
+        "converts"            = sapling.map_or(0, |b|b.shielded_converts.len() as u32),
+        "outputs"             = sapling.map_or(0, |b|b.shielded_outputs.len() as u32),
+        "transparentInputs"   = transparent.map_or(0, |b|b.vin.len() as u32),
+        "transparentOutputs"  = transparent.map_or(0, |b|b.vout.len() as u32),
+    }))
+}
+
+/// Classify a JSON memo by the well-known keys it contains.
+fn json_memo (text: &str, json: &Map<String, Json>) -> Result<Object, DecodeError> {
+    let forward       = json.get("forward").map(forward).transpose()?;
+    let wasm          = json.get("wasm").map(wasm_hook).transpose()?;
+    let src_callback  = json.get("src_callback").map(callback).transpose()?;
+    let dest_callback = json.get("dest_callback").map(callback).transpose()?;
+    let kind = if forward.is_some() {
+        "forward"
+    } else if wasm.is_some() {
+        "wasm-hook"
+    } else if src_callback.is_some() || dest_callback.is_some() {
+        "callback"
+    } else {
+        "json"
+    };
+    let mut memo = to_object! {
+        "kind" = kind,
+        "text" = text,
+        "json" = Value::from(&Json::Object(json.clone())),
+    };
+    for (key, value) in [
+        ("forward",      forward),
+        ("wasm",         wasm),
+        ("srcCallback",  src_callback),
+        ("destCallback", dest_callback),
+    ] {
+        if let Some(value) = value {
+            memo.set(key, value.into());
+        }
+    }
+    Ok(memo)
+}
+
+/// Get a field of a JSON object, if it's there.
+fn field (json: &Json, key: &str) -> Option<Value> {
+    json.get(key).map(Value::from)
+}
+
+/// Get a field that is a 64-bit integer upstream, output like the integers
+/// of the message if it's a JSON number, or as is otherwise.
+fn integer_field (json: &Json, key: &str) -> Option<Value> {
+    let number = json.get(key)?;
+    match number.as_i64().map(i128::from).or_else(||number.as_u64().map(i128::from)) {
+        Some(integer) => Some(Options::current().integers.to_value(integer)),
+        None => field(json, key),
+    }
+}
+
+/// Packet-forward-middleware: the next hop, and the memo to forward with it.
+fn forward (json: &Json) -> Result<Object, DecodeError> {
+    let next = match json.get("next") {
+        Some(Json::String(next)) => Some(Memo(next).to_js()?),
+        Some(next @ Json::Object(_)) => Some(Memo(&next.to_string()).to_js()?),
+        _ => None,
+    };
+    Ok(to_object! {
+        "receiver" = field(json, "receiver"),
+        "port"     = field(json, "port"),
+        "channel"  = field(json, "channel"),
+        "timeout"  = integer_field(json, "timeout"),
+        "retries"  = field(json, "retries"),
+        "next"     = next,
+    })
+}
+
+/// IBC hooks: the contract to call on arrival, and the message to call it with.
+fn wasm_hook (json: &Json) -> Result<Object, DecodeError> {
+    Ok(to_object! {
+        "contract" = field(json, "contract"),
+        "msg"      = field(json, "msg"),
+    })
+}
+
+/// ADR-8 callbacks: the contract to call back, and the gas limit for the call.
+fn callback (json: &Json) -> Result<Object, DecodeError> {
+    Ok(to_object! {
+        "address"  = field(json, "address"),
+        "gasLimit" = integer_field(json, "gas_limit"),
+    })
+}
@@ -0,0 +1,130 @@
+//! Options that control how decoded messages are converted to JS.
+//!
+//! The options are in effect for the duration of [`Options::scope`], so that
+//! `ToJS` impls deep in the tree can consult them with [`Options::current`].
+
+use std::cell::RefCell;
+use crate::{DecodeError, Value};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    /// How to output Tendermint validator sets.
+    pub validator_sets: ValidatorSets,
+    /// How to output 64-bit integers, such as heights, sequences and timestamps.
+    pub integers: Integers,
+    /// Which structure to output messages in.
+    pub format: Format,
+    /// Whether nested decode failures are output in place of the failing field,
+    /// instead of failing the whole message. See [`crate::lenient`].
+    pub lenient: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ValidatorSets {
+    /// Every validator, the proposer, the total voting power, and the hash.
+    #[default]
+    Full,
+    /// Only the hash of the set.
+    Hash,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Integers {
+    /// As `BigInt`s, so that values above 2^53 keep their precision.
+    #[default]
+    BigInt,
+    /// As decimal strings, for consumers that can't handle `BigInt`s.
+    String,
+}
+
+impl Integers {
+    /// Output a 64-bit integer in this representation.
+    pub fn to_value (self, value: i128) -> Value {
+        match self {
+            Self::BigInt => Value::BigInt(value),
+            Self::String => Value::from(value.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    /// The structure described in [`crate::schema`].
+    #[default]
+    Native,
+    /// Canonical proto3 JSON, see [`crate::proto3`].
+    Proto3,
+}
+
+thread_local! {
+    static OPTIONS: RefCell<Options> = RefCell::new(Options::default());
+}
+
+impl Options {
+    /// Get the options currently in effect.
+    pub fn current () -> Self {
+        OPTIONS.with_borrow(Clone::clone)
+    }
+
+    /// Run a function with these options in effect.
+    pub fn scope<T> (self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Options>);
+        impl Drop for Restore {
+            fn drop (&mut self) {
+                if let Some(options) = self.0.take() {
+                    OPTIONS.set(options);
+                }
+            }
+        }
+        let _restore = Restore(Some(OPTIONS.replace(self)));
+        f()
+    }
+
+    /// Parse options passed from JS, such as `{ validatorSets: "hash", integers: "string" }`.
+    /// Missing options keep their defaults.
+    pub fn from_js (options: Option<&js_sys::Object>) -> Result<Self, DecodeError> {
+        let mut result = Self::default();
+        let Some(options) = options else {
+            return Ok(result)
+        };
+        let get = |key: &str| js_sys::Reflect::get(options, &key.into())
+            .map_err(|e|DecodeError::input(crate::js::error_message(&e)));
+        let validator_sets = get("validatorSets")?;
+        if !validator_sets.is_undefined() {
+            result.validator_sets = match validator_sets.as_string().as_deref() {
+                Some("full") => ValidatorSets::Full,
+                Some("hash") => ValidatorSets::Hash,
+                _ => return Err(DecodeError::input(format!(
+                    "invalid option validatorSets: {validator_sets:?} (expected \"full\" or \"hash\")"
+                ))),
+            };
+        }
+        let integers = get("integers")?;
+        if !integers.is_undefined() {
+            result.integers = match integers.as_string().as_deref() {
+                Some("bigint") => Integers::BigInt,
+                Some("string") => Integers::String,
+                _ => return Err(DecodeError::input(format!(
+                    "invalid option integers: {integers:?} (expected \"bigint\" or \"string\")"
+                ))),
+            };
+        }
+        let format = get("format")?;
+        if !format.is_undefined() {
+            result.format = match format.as_string().as_deref() {
+                Some("native") => Format::Native,
+                Some("proto3") => Format::Proto3,
+                _ => return Err(DecodeError::input(format!(
+                    "invalid option format: {format:?} (expected \"native\" or \"proto3\")"
+                ))),
+            };
+        }
+        let lenient = get("lenient")?;
+        if !lenient.is_undefined() {
+            result.lenient = lenient.as_bool().ok_or_else(||DecodeError::input(format!(
+                "invalid option lenient: {lenient:?} (expected a boolean)"
+            )))?;
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,565 @@
+//! Canonical proto3 JSON output, as produced by ibc-go and cosmjs.
+//!
+//! Messages are output with their `@type` URL and their protobuf field names in
+//! snake_case; bytes are base64, and 64-bit integers are decimal strings. Default
+//! values are emitted rather than omitted, so that every message has all its fields.
+//! Fields that ibc-rs doesn't keep, such as upgrade sequences, are output as their
+//! defaults. Right after `@type` comes the `schemaVersion` of the output, which isn't
+//! a protobuf field, but tells apart the outputs of different versions of the decoder.
+//!
+//! Nested `Any` values of the Tendermint light client types are expanded, with their
+//! fields next to their `@type`. Other `Any` values are output as `{ "@type", "value" }`,
+//! with the value as base64, since their contents can't be expanded without knowing
+//! their type; decoders registered at runtime produce native output, and aren't used here.
+//! The Namada transfer attached to `MsgTransfer` has no protobuf counterpart,
+//! and is not part of this output.
+
+use base64::prelude::*;
+use serde_json::{json, Value as Json};
+use namada_sdk::ibc::{
+    core::{
+        client::{context::types::msgs::*, types::Height},
+        connection::types::{msgs::*, Counterparty, version::Version},
+        channel::types::{
+            msgs::*,
+            packet::Packet,
+            timeout::{TimeoutHeight, TimeoutTimestamp},
+        },
+        commitment_types::commitment::CommitmentProofBytes,
+    },
+    primitives::proto::Any,
+};
+use ibc_proto::ibc::{
+    core::client::v1::Height as RawHeight,
+    lightclients::tendermint::v1 as tendermint,
+};
+use namada_sdk::tendermint_proto::types as tm;
+use crate::{DecodedIbc, DecodeError, ErrorKind, Stage, schema::VERSION};
+
+/// Render a decoded message as proto3 JSON.
+pub fn to_json (decoded: &DecodedIbc) -> Result<Json, DecodeError> {
+    let Json::Object(fields) = message(decoded)? else {
+        unreachable!("messages are JSON objects")
+    };
+    let mut output = serde_json::Map::new();
+    for (key, value) in fields {
+        let is_type = key == "@type";
+        output.insert(key, value);
+        if is_type {
+            output.insert("schemaVersion".into(), Json::from(VERSION));
+        }
+    }
+    Ok(Json::Object(output))
+}
+
+/// The protobuf fields of a message, with its `@type`.
+fn message (decoded: &DecodedIbc) -> Result<Json, DecodeError> {
+    Ok(match decoded {
+        DecodedIbc::Client(message) => match message {
+            ClientMsg::CreateClient(message) => json!({
+                "@type":           "/ibc.core.client.v1.MsgCreateClient",
+                "client_state":    any(&message.client_state, "client_state")?,
+                "consensus_state": any(&message.consensus_state, "consensus_state")?,
+                "signer":          message.signer.as_ref(),
+            }),
+            ClientMsg::UpdateClient(message) => json!({
+                "@type":          "/ibc.core.client.v1.MsgUpdateClient",
+                "client_id":      message.client_id.as_str(),
+                "client_message": any(&message.client_message, "client_message")?,
+                "signer":         message.signer.as_ref(),
+            }),
+            ClientMsg::Misbehaviour(message) => json!({
+                "@type":        "/ibc.core.client.v1.MsgSubmitMisbehaviour",
+                "client_id":    message.client_id.as_str(),
+                "misbehaviour": any(&message.misbehaviour, "misbehaviour")?,
+                "signer":       message.signer.as_ref(),
+            }),
+            ClientMsg::UpgradeClient(message) => json!({
+                "@type":                         "/ibc.core.client.v1.MsgUpgradeClient",
+                "client_id":                     message.client_id.as_str(),
+                "client_state":                  any(&message.upgraded_client_state, "client_state")?,
+                "consensus_state":               any(&message.upgraded_consensus_state, "consensus_state")?,
+                "proof_upgrade_client":          proof(&message.proof_upgrade_client),
+                "proof_upgrade_consensus_state": proof(&message.proof_upgrade_consensus_state),
+                "signer":                        message.signer.as_ref(),
+            }),
+            ClientMsg::RecoverClient(message) => json!({
+                "@type":                "/ibc.core.client.v1.MsgRecoverClient",
+                "subject_client_id":    message.subject_client_id.as_str(),
+                "substitute_client_id": message.substitute_client_id.as_str(),
+                "signer":               message.signer.as_ref(),
+            }),
+        },
+
+        DecodedIbc::Connection(message) => match message {
+            ConnectionMsg::OpenInit(message) => json!({
+                "@type":        "/ibc.core.connection.v1.MsgConnectionOpenInit",
+                "client_id":    message.client_id_on_a.as_str(),
+                "counterparty": counterparty(&message.counterparty),
+                "version":      message.version.as_ref().map(version).transpose()?,
+                "delay_period": message.delay_period.as_nanos().to_string(),
+                "signer":       message.signer.as_ref(),
+            }),
+            ConnectionMsg::OpenTry(message) => {
+                #[allow(deprecated)]
+                let (previous_connection_id, host_consensus_state_proof) = (
+                    &message.previous_connection_id,
+                    &message.proof_consensus_state_of_b,
+                );
+                json!({
+                    "@type":                      "/ibc.core.connection.v1.MsgConnectionOpenTry",
+                    "client_id":                  message.client_id_on_b.as_str(),
+                    "previous_connection_id":     previous_connection_id,
+                    "client_state":               any(&message.client_state_of_b_on_a, "client_state")?,
+                    "counterparty":               counterparty(&message.counterparty),
+                    "delay_period":               message.delay_period.as_nanos().to_string(),
+                    "counterparty_versions":      message.versions_on_a.iter()
+                        .map(version)
+                        .collect::<Result<Vec<_>, _>>()?,
+                    "proof_height":               height(&message.proofs_height_on_a),
+                    "proof_init":                 proof(&message.proof_conn_end_on_a),
+                    "proof_client":               proof(&message.proof_client_state_of_b_on_a),
+                    "proof_consensus":            proof(&message.proof_consensus_state_of_b_on_a),
+                    "consensus_height":           height(&message.consensus_height_of_b_on_a),
+                    "signer":                     message.signer.as_ref(),
+                    "host_consensus_state_proof": optional_proof(host_consensus_state_proof),
+                })
+            },
+            ConnectionMsg::OpenAck(message) => json!({
+                "@type":                      "/ibc.core.connection.v1.MsgConnectionOpenAck",
+                "connection_id":              message.conn_id_on_a.as_str(),
+                "counterparty_connection_id": message.conn_id_on_b.as_str(),
+                "version":                    version(&message.version)?,
+                "client_state":               any(&message.client_state_of_a_on_b, "client_state")?,
+                "proof_height":               height(&message.proofs_height_on_b),
+                "proof_try":                  proof(&message.proof_conn_end_on_b),
+                "proof_client":               proof(&message.proof_client_state_of_a_on_b),
+                "proof_consensus":            proof(&message.proof_consensus_state_of_a_on_b),
+                "consensus_height":           height(&message.consensus_height_of_a_on_b),
+                "signer":                     message.signer.as_ref(),
+                "host_consensus_state_proof": optional_proof(&message.proof_consensus_state_of_a),
+            }),
+            ConnectionMsg::OpenConfirm(message) => json!({
+                "@type":         "/ibc.core.connection.v1.MsgConnectionOpenConfirm",
+                "connection_id": message.conn_id_on_b.as_str(),
+                "proof_ack":     proof(&message.proof_conn_end_on_a),
+                "proof_height":  height(&message.proof_height_on_a),
+                "signer":        message.signer.as_ref(),
+            }),
+        },
+
+        DecodedIbc::Channel(message) => match message {
+            ChannelMsg::OpenInit(message) => json!({
+                "@type":   "/ibc.core.channel.v1.MsgChannelOpenInit",
+                "port_id": message.port_id_on_a.as_str(),
+                "channel": {
+                    "state":            "STATE_INIT",
+                    "ordering":         message.ordering.as_str(),
+                    "counterparty":     {
+                        "port_id":    message.port_id_on_b.as_str(),
+                        "channel_id": "",
+                    },
+                    "connection_hops":  message.connection_hops_on_a.iter()
+                        .map(|hop|hop.as_str())
+                        .collect::<Vec<_>>(),
+                    "version":          message.version_proposal.as_str(),
+                    "upgrade_sequence": "0",
+                },
+                "signer":  message.signer.as_ref(),
+            }),
+            ChannelMsg::OpenTry(message) => {
+                #[allow(deprecated)]
+                let version_proposal = &message.version_proposal;
+                json!({
+                    "@type":                "/ibc.core.channel.v1.MsgChannelOpenTry",
+                    "port_id":              message.port_id_on_b.as_str(),
+                    "previous_channel_id":  "",
+                    "channel":              {
+                        "state":            "STATE_TRYOPEN",
+                        "ordering":         message.ordering.as_str(),
+                        "counterparty":     {
+                            "port_id":    message.port_id_on_a.as_str(),
+                            "channel_id": message.chan_id_on_a.as_str(),
+                        },
+                        "connection_hops":  message.connection_hops_on_b.iter()
+                            .map(|hop|hop.as_str())
+                            .collect::<Vec<_>>(),
+                        "version":          version_proposal.as_str(),
+                        "upgrade_sequence": "0",
+                    },
+                    "counterparty_version": message.version_supported_on_a.as_str(),
+                    "proof_init":           proof(&message.proof_chan_end_on_a),
+                    "proof_height":         height(&message.proof_height_on_a),
+                    "signer":               message.signer.as_ref(),
+                })
+            },
+            ChannelMsg::OpenAck(message) => json!({
+                "@type":                   "/ibc.core.channel.v1.MsgChannelOpenAck",
+                "port_id":                 message.port_id_on_a.as_str(),
+                "channel_id":              message.chan_id_on_a.as_str(),
+                "counterparty_channel_id": message.chan_id_on_b.as_str(),
+                "counterparty_version":    message.version_on_b.as_str(),
+                "proof_try":               proof(&message.proof_chan_end_on_b),
+                "proof_height":            height(&message.proof_height_on_b),
+                "signer":                  message.signer.as_ref(),
+            }),
+            ChannelMsg::OpenConfirm(message) => json!({
+                "@type":        "/ibc.core.channel.v1.MsgChannelOpenConfirm",
+                "port_id":      message.port_id_on_b.as_str(),
+                "channel_id":   message.chan_id_on_b.as_str(),
+                "proof_ack":    proof(&message.proof_chan_end_on_a),
+                "proof_height": height(&message.proof_height_on_a),
+                "signer":       message.signer.as_ref(),
+            }),
+            ChannelMsg::CloseInit(message) => json!({
+                "@type":      "/ibc.core.channel.v1.MsgChannelCloseInit",
+                "port_id":    message.port_id_on_a.as_str(),
+                "channel_id": message.chan_id_on_a.as_str(),
+                "signer":     message.signer.as_ref(),
+            }),
+            ChannelMsg::CloseConfirm(message) => json!({
+                "@type":                         "/ibc.core.channel.v1.MsgChannelCloseConfirm",
+                "port_id":                       message.port_id_on_b.as_str(),
+                "channel_id":                    message.chan_id_on_b.as_str(),
+                "proof_init":                    proof(&message.proof_chan_end_on_a),
+                "proof_height":                  height(&message.proof_height_on_a),
+                "signer":                        message.signer.as_ref(),
+                "counterparty_upgrade_sequence": "0",
+            }),
+        },
+
+        DecodedIbc::Packet(message) => match message {
+            PacketMsg::Recv(message) => json!({
+                "@type":            "/ibc.core.channel.v1.MsgRecvPacket",
+                "packet":           packet(&message.packet),
+                "proof_commitment": proof(&message.proof_commitment_on_a),
+                "proof_height":     height(&message.proof_height_on_a),
+                "signer":           message.signer.as_ref(),
+            }),
+            PacketMsg::Ack(message) => json!({
+                "@type":           "/ibc.core.channel.v1.MsgAcknowledgement",
+                "packet":          packet(&message.packet),
+                "acknowledgement": bytes(message.acknowledgement.as_bytes()),
+                "proof_acked":     proof(&message.proof_acked_on_b),
+                "proof_height":    height(&message.proof_height_on_b),
+                "signer":          message.signer.as_ref(),
+            }),
+            PacketMsg::Timeout(message) => json!({
+                "@type":              "/ibc.core.channel.v1.MsgTimeout",
+                "packet":             packet(&message.packet),
+                "proof_unreceived":   proof(&message.proof_unreceived_on_b),
+                "proof_height":       height(&message.proof_height_on_b),
+                "next_sequence_recv": message.next_seq_recv_on_b.value().to_string(),
+                "signer":             message.signer.as_ref(),
+            }),
+            PacketMsg::TimeoutOnClose(message) => json!({
+                "@type":                         "/ibc.core.channel.v1.MsgTimeoutOnClose",
+                "packet":                        packet(&message.packet),
+                "proof_unreceived":              proof(&message.proof_unreceived_on_b),
+                "proof_close":                   proof(&message.proof_close_on_b),
+                "proof_height":                  height(&message.proof_height_on_b),
+                "next_sequence_recv":            message.next_seq_recv_on_b.value().to_string(),
+                "signer":                        message.signer.as_ref(),
+                "counterparty_upgrade_sequence": "0",
+            }),
+        },
+
+        DecodedIbc::Transfer(message) => {
+            let message = &message.message;
+            json!({
+                "@type":             "/ibc.applications.transfer.v1.MsgTransfer",
+                "source_port":       message.port_id_on_a.as_str(),
+                "source_channel":    message.chan_id_on_a.as_str(),
+                "token":             {
+                    "denom":  message.packet_data.token.denom.to_string(),
+                    "amount": message.packet_data.token.amount.to_string(),
+                },
+                "sender":            message.packet_data.sender.as_ref(),
+                "receiver":          message.packet_data.receiver.as_ref(),
+                "timeout_height":    timeout_height(&message.timeout_height_on_b),
+                "timeout_timestamp": timeout_timestamp(&message.timeout_timestamp_on_b),
+                "memo":              message.packet_data.memo.to_string(),
+            })
+        },
+
+        DecodedIbc::NftTransfer(message) => {
+            let message = &message.message;
+            json!({
+                "@type":             "/ibc.applications.nft_transfer.v1.MsgTransfer",
+                "source_port":       message.port_id_on_a.as_str(),
+                "source_channel":    message.chan_id_on_a.as_str(),
+                "class_id":          message.packet_data.class_id.to_string(),
+                "token_ids":         message.packet_data.token_ids.0.iter()
+                    .map(|token_id|token_id.to_string())
+                    .collect::<Vec<_>>(),
+                "sender":            message.packet_data.sender.as_ref(),
+                "receiver":          message.packet_data.receiver.as_ref(),
+                "timeout_height":    timeout_height(&message.timeout_height_on_b),
+                "timeout_timestamp": timeout_timestamp(&message.timeout_timestamp_on_b),
+                "memo":              message.packet_data.memo.as_ref()
+                    .map(|memo|memo.to_string())
+                    .unwrap_or_default(),
+            })
+        },
+    })
+}
+
+fn bytes (bytes: &[u8]) -> Json {
+    Json::from(BASE64_STANDARD.encode(bytes))
+}
+
+fn proof (proof: &CommitmentProofBytes) -> Json {
+    bytes(&Vec::<u8>::from(proof.clone()))
+}
+
+fn optional_proof (proof: &Option<CommitmentProofBytes>) -> Json {
+    proof.as_ref().map_or_else(||bytes(&[]), self::proof)
+}
+
+/// The `Any` in the field with the given key. Known types are expanded next to
+/// their `@type`; the others keep their value as base64.
+fn any (any: &Any, key: &str) -> Result<Json, DecodeError> {
+    use namada_sdk::ibc::clients::tendermint::types::{
+        TENDERMINT_HEADER_TYPE_URL, TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+        TENDERMINT_CLIENT_STATE_TYPE_URL, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+    };
+    let fields = match any.type_url.as_str() {
+        TENDERMINT_CLIENT_STATE_TYPE_URL    => Ok(client_state(&decode(any)?)),
+        TENDERMINT_CONSENSUS_STATE_TYPE_URL => consensus_state(&decode(any)?),
+        TENDERMINT_HEADER_TYPE_URL          => header(&decode(any)?),
+        TENDERMINT_MISBEHAVIOUR_TYPE_URL    => misbehaviour(&decode(any)?),
+        _ => Ok(json!({ "value": bytes(&any.value) })),
+    };
+    let Json::Object(fields) = fields.map_err(|e|e.in_any(&any.type_url).at(key))? else {
+        unreachable!("messages are JSON objects")
+    };
+    let mut output = serde_json::Map::new();
+    output.insert("@type".into(), Json::from(any.type_url.as_str()));
+    output.extend(fields);
+    Ok(Json::Object(output))
+}
+
+/// Decode the value of an `Any` into its protobuf type.
+fn decode<T: prost::Message + Default> (any: &Any) -> Result<T, DecodeError> {
+    T::decode(any.value.as_slice()).map_err(|e|{
+        DecodeError::new(ErrorKind::Protobuf, Stage::Any, format!("{e}")).with_type_url(&any.type_url)
+    })
+}
+
+fn client_state (state: &tendermint::ClientState) -> Json {
+    #[allow(deprecated)]
+    let (allow_update_after_expiry, allow_update_after_misbehaviour) = (
+        state.allow_update_after_expiry,
+        state.allow_update_after_misbehaviour,
+    );
+    json!({
+        "chain_id":                        state.chain_id,
+        "trust_level":                     state.trust_level.as_ref().map(|level|json!({
+            "numerator":   level.numerator.to_string(),
+            "denominator": level.denominator.to_string(),
+        })),
+        "trusting_period":                 state.trusting_period.as_ref()
+            .map(|period|duration(period.seconds, period.nanos)),
+        "unbonding_period":                state.unbonding_period.as_ref()
+            .map(|period|duration(period.seconds, period.nanos)),
+        "max_clock_drift":                 state.max_clock_drift.as_ref()
+            .map(|drift|duration(drift.seconds, drift.nanos)),
+        "frozen_height":                   state.frozen_height.as_ref().map(raw_height),
+        "latest_height":                   state.latest_height.as_ref().map(raw_height),
+        "proof_specs":                     state.proof_specs.iter().map(|spec|json!({
+            "leaf_spec":                     spec.leaf_spec.as_ref().map(|leaf|json!({
+                "hash":          leaf.hash().as_str_name(),
+                "prehash_key":   leaf.prehash_key().as_str_name(),
+                "prehash_value": leaf.prehash_value().as_str_name(),
+                "length":        leaf.length().as_str_name(),
+                "prefix":        bytes(&leaf.prefix),
+            })),
+            "inner_spec":                    spec.inner_spec.as_ref().map(|inner|json!({
+                "child_order":       inner.child_order,
+                "child_size":        inner.child_size,
+                "min_prefix_length": inner.min_prefix_length,
+                "max_prefix_length": inner.max_prefix_length,
+                "empty_child":       bytes(&inner.empty_child),
+                "hash":              inner.hash().as_str_name(),
+            })),
+            "max_depth":                     spec.max_depth,
+            "min_depth":                     spec.min_depth,
+            "prehash_key_before_comparison": spec.prehash_key_before_comparison,
+        })).collect::<Vec<_>>(),
+        "upgrade_path":                    state.upgrade_path,
+        "allow_update_after_expiry":       allow_update_after_expiry,
+        "allow_update_after_misbehaviour": allow_update_after_misbehaviour,
+    })
+}
+
+fn consensus_state (state: &tendermint::ConsensusState) -> Result<Json, DecodeError> {
+    Ok(json!({
+        "timestamp":            state.timestamp.as_ref()
+            .map(|time|timestamp(time.seconds, time.nanos))
+            .transpose()?,
+        "root":                 state.root.as_ref().map(|root|json!({ "hash": bytes(&root.hash) })),
+        "next_validators_hash": bytes(&state.next_validators_hash),
+    }))
+}
+
+fn header (header: &tendermint::Header) -> Result<Json, DecodeError> {
+    Ok(json!({
+        "signed_header":      header.signed_header.as_ref().map(signed_header).transpose()?,
+        "validator_set":      header.validator_set.as_ref().map(validator_set),
+        "trusted_height":     header.trusted_height.as_ref().map(raw_height),
+        "trusted_validators": header.trusted_validators.as_ref().map(validator_set),
+    }))
+}
+
+fn misbehaviour (misbehaviour: &tendermint::Misbehaviour) -> Result<Json, DecodeError> {
+    #[allow(deprecated)]
+    let client_id = &misbehaviour.client_id;
+    Ok(json!({
+        "client_id": client_id,
+        "header_1":  misbehaviour.header_1.as_ref().map(header).transpose()?,
+        "header_2":  misbehaviour.header_2.as_ref().map(header).transpose()?,
+    }))
+}
+
+fn signed_header (signed_header: &tm::SignedHeader) -> Result<Json, DecodeError> {
+    let header = signed_header.header.as_ref().map(|header|Ok::<_, DecodeError>(json!({
+        "version":              header.version.as_ref().map(|version|json!({
+            "block": version.block.to_string(),
+            "app":   version.app.to_string(),
+        })),
+        "chain_id":             header.chain_id,
+        "height":               header.height.to_string(),
+        "time":                 header.time.as_ref()
+            .map(|time|timestamp(time.seconds, time.nanos))
+            .transpose()?,
+        "last_block_id":        header.last_block_id.as_ref().map(block_id),
+        "last_commit_hash":     bytes(&header.last_commit_hash),
+        "data_hash":            bytes(&header.data_hash),
+        "validators_hash":      bytes(&header.validators_hash),
+        "next_validators_hash": bytes(&header.next_validators_hash),
+        "consensus_hash":       bytes(&header.consensus_hash),
+        "app_hash":             bytes(&header.app_hash),
+        "last_results_hash":    bytes(&header.last_results_hash),
+        "evidence_hash":        bytes(&header.evidence_hash),
+        "proposer_address":     bytes(&header.proposer_address),
+    }))).transpose()?;
+    let commit = signed_header.commit.as_ref().map(|commit|Ok::<_, DecodeError>(json!({
+        "height":     commit.height.to_string(),
+        "round":      commit.round,
+        "block_id":   commit.block_id.as_ref().map(block_id),
+        "signatures": commit.signatures.iter().map(|signature|Ok(json!({
+            "block_id_flag":     signature.block_id_flag().as_str_name(),
+            "validator_address": bytes(&signature.validator_address),
+            "timestamp":         signature.timestamp.as_ref()
+                .map(|time|timestamp(time.seconds, time.nanos))
+                .transpose()?,
+            "signature":         bytes(&signature.signature),
+        }))).collect::<Result<Vec<_>, DecodeError>>()?,
+    }))).transpose()?;
+    Ok(json!({ "header": header, "commit": commit }))
+}
+
+fn block_id (block_id: &tm::BlockId) -> Json {
+    json!({
+        "hash":            bytes(&block_id.hash),
+        "part_set_header": block_id.part_set_header.as_ref().map(|parts|json!({
+            "total": parts.total,
+            "hash":  bytes(&parts.hash),
+        })),
+    })
+}
+
+fn validator_set (set: &tm::ValidatorSet) -> Json {
+    json!({
+        "validators":         set.validators.iter().map(validator).collect::<Vec<_>>(),
+        "proposer":           set.proposer.as_ref().map(validator),
+        "total_voting_power": set.total_voting_power.to_string(),
+    })
+}
+
+fn validator (validator: &tm::Validator) -> Json {
+    use namada_sdk::tendermint_proto::crypto::public_key::Sum;
+    json!({
+        "address":           bytes(&validator.address),
+        "pub_key":           validator.pub_key.as_ref().map(|key|match &key.sum {
+            Some(Sum::Ed25519(key))   => json!({ "ed25519": bytes(key) }),
+            Some(Sum::Secp256k1(key)) => json!({ "secp256k1": bytes(key) }),
+            None                      => json!({}),
+        }),
+        "voting_power":      validator.voting_power.to_string(),
+        "proposer_priority": validator.proposer_priority.to_string(),
+    })
+}
+
+/// A `google.protobuf.Timestamp`, as an RFC 3339 string.
+fn timestamp (seconds: i64, nanos: i32) -> Result<Json, DecodeError> {
+    let nanos = u32::try_from(nanos).map_err(|e|DecodeError::conversion(Stage::Any, format!("{e}")))?;
+    let time = namada_sdk::tendermint::Time::from_unix_timestamp(seconds, nanos)
+        .map_err(|e|DecodeError::conversion(Stage::Any, format!("{e}")))?;
+    Ok(Json::from(time.to_rfc3339()))
+}
+
+/// A `google.protobuf.Duration`, as seconds with 0, 3, 6 or 9 fractional digits.
+fn duration (seconds: i64, nanos: i32) -> Json {
+    let sign = if seconds < 0 || nanos < 0 { "-" } else { "" };
+    let fraction = match nanos.unsigned_abs() {
+        0 => String::new(),
+        nanos if nanos % 1_000_000 == 0 => format!(".{:03}", nanos / 1_000_000),
+        nanos if nanos % 1_000 == 0 => format!(".{:06}", nanos / 1_000),
+        nanos => format!(".{nanos:09}"),
+    };
+    Json::from(format!("{sign}{}{fraction}s", seconds.unsigned_abs()))
+}
+
+fn height (height: &Height) -> Json {
+    json!({
+        "revision_number": height.revision_number().to_string(),
+        "revision_height": height.revision_height().to_string(),
+    })
+}
+
+fn raw_height (height: &RawHeight) -> Json {
+    json!({
+        "revision_number": height.revision_number.to_string(),
+        "revision_height": height.revision_height.to_string(),
+    })
+}
+
+fn timeout_height (timeout: &TimeoutHeight) -> Json {
+    match timeout {
+        TimeoutHeight::Never => json!({ "revision_number": "0", "revision_height": "0" }),
+        TimeoutHeight::At(at) => height(at),
+    }
+}
+
+fn timeout_timestamp (timeout: &TimeoutTimestamp) -> Json {
+    match timeout {
+        TimeoutTimestamp::Never => Json::from("0"),
+        TimeoutTimestamp::At(at) => Json::from(at.nanoseconds().to_string()),
+    }
+}
+
+fn version (version: &Version) -> Result<Json, DecodeError> {
+    serde_json::to_value(version).map_err(|e|DecodeError::conversion(Stage::Envelope, format!("{e}")))
+}
+
+fn counterparty (counterparty: &Counterparty) -> Json {
+    json!({
+        "client_id":     counterparty.client_id.as_str(),
+        "connection_id": counterparty.connection_id.as_ref().map_or("", |id|id.as_str()),
+        "prefix":        { "key_prefix": bytes(counterparty.prefix.as_bytes()) },
+    })
+}
+
+fn packet (packet: &Packet) -> Json {
+    json!({
+        "sequence":            packet.seq_on_a.value().to_string(),
+        "source_port":         packet.port_id_on_a.as_str(),
+        "source_channel":      packet.chan_id_on_a.as_str(),
+        "destination_port":    packet.port_id_on_b.as_str(),
+        "destination_channel": packet.chan_id_on_b.as_str(),
+        "data":                bytes(&packet.data),
+        "timeout_height":      timeout_height(&packet.timeout_height_on_b),
+        "timeout_timestamp":   timeout_timestamp(&packet.timeout_timestamp_on_b),
+    })
+}
@@ -0,0 +1,73 @@
+//! Registry of decoders for `Any` values, keyed by type URL.
+//!
+//! `ToJS for Any` consults this before falling back to `{ typeUrl, value }`.
+//! The Tendermint light client types are registered by default; more can be
+//! added from Rust with [`register`], or from JS with `Decode.registerTypeUrl`.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use namada_sdk::ibc::primitives::proto::Any;
+use crate::{Value, DecodeError, ErrorKind, Stage, ToJS};
+
+/// Decodes the `value` of an `Any`.
+pub type AnyDecoder = Rc<dyn Fn(&Any) -> Result<Value, DecodeError>>;
+
+thread_local! {
+    static REGISTRY: RefCell<HashMap<String, AnyDecoder>> = RefCell::new(builtins());
+}
+
+/// Register a decoder for `Any` values with the given type URL.
+/// This replaces any previously registered decoder, including a built-in one.
+pub fn register (type_url: &str, decoder: AnyDecoder) {
+    REGISTRY.with_borrow_mut(|registry|registry.insert(type_url.to_string(), decoder));
+}
+
+/// Remove the decoder for the given type URL. Returns whether there was one.
+pub fn unregister (type_url: &str) -> bool {
+    REGISTRY.with_borrow_mut(|registry|registry.remove(type_url).is_some())
+}
+
+/// Get the decoder for the given type URL, if any.
+pub fn get (type_url: &str) -> Option<AnyDecoder> {
+    // The decoder is cloned out, so that it can itself use the registry.
+    REGISTRY.with_borrow(|registry|registry.get(type_url).cloned())
+}
+
+/// List the type URLs that have a decoder.
+pub fn type_urls () -> Vec<String> {
+    let mut type_urls: Vec<_> = REGISTRY.with_borrow(|registry|registry.keys().cloned().collect());
+    type_urls.sort();
+    type_urls
+}
+
+/// Decode the value of an `Any` into its protobuf type, convert that
+/// into a domain type, and convert that to JS.
+pub fn decode_as<Raw, T> (any: &Any) -> Result<Value, DecodeError>
+where
+    Raw: prost::Message + Default,
+    T: TryFrom<Raw> + ToJS,
+    T::Error: std::error::Error + 'static,
+{
+    let raw = Raw::decode(any.value.as_slice()).map_err(|e|{
+        DecodeError::new(ErrorKind::Protobuf, Stage::Any, format!("{e}")).with_type_url(&any.type_url)
+    })?;
+    let value = T::try_from(raw).map_err(|e|{
+        DecodeError::new(ErrorKind::of(&e), Stage::Any, format!("{e}")).with_type_url(&any.type_url)
+    })?;
+    value.to_js().map_err(|e|e.in_any(&any.type_url))
+}
+
+fn builtins () -> HashMap<String, AnyDecoder> {
+    use ibc_proto::ibc::lightclients::tendermint::v1 as raw;
+    use namada_sdk::ibc::clients::tendermint::types::{
+        Header, ClientState, ConsensusState, Misbehaviour,
+        TENDERMINT_HEADER_TYPE_URL, TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+        TENDERMINT_CLIENT_STATE_TYPE_URL, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+    };
+    let builtins: [(&str, AnyDecoder); 4] = [
+        (TENDERMINT_HEADER_TYPE_URL,          Rc::new(decode_as::<raw::Header, Header>)),
+        (TENDERMINT_MISBEHAVIOUR_TYPE_URL,    Rc::new(decode_as::<raw::Misbehaviour, Misbehaviour>)),
+        (TENDERMINT_CLIENT_STATE_TYPE_URL,    Rc::new(decode_as::<raw::ClientState, ClientState>)),
+        (TENDERMINT_CONSENSUS_STATE_TYPE_URL, Rc::new(decode_as::<raw::ConsensusState, ConsensusState>)),
+    ];
+    builtins.into_iter().map(|(type_url, decoder)|(type_url.to_string(), decoder)).collect()
+}
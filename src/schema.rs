@@ -0,0 +1,457 @@
+//! The output schema of `Decode.ibc`.
+//!
+//! Every decoded message is an object with a `type`, the `schemaVersion` it conforms to,
+//! and the fields listed here for that type, in this order. Field types are given in
+//! TypeScript notation; the named types (`Any`, `Height`, `Packet`...) are the shapes
+//! output by the corresponding `ToJS` impls.
+//!
+//! Messages decoded with `{ lenient: true }` also have a `warnings` list,
+//! see [`crate::lenient`].
+//!
+//! Any change to the keys or shapes of the output must bump [`VERSION`], and update
+//! the golden output of every message type in `tests/golden`, with `just golden`.
+//!
+//! Version history:
+//!
+//! 1. Original, unversioned output.
+//! 2. Added `schemaVersion`. Fixed `chanIdOnA` in `envelope.channel.open_ack`,
+//!    `clientStateOfBOnA`, `proofConsensusStateOfBOnA` and `consensusHeightOfBOnA`
+//!    in `envelope.connection.open_try`, and the keys of `Height`, which are now
+//!    `revisionNumber` and `revisionHeight`. Connection versions are output as
+//!    `{ identifier, features }` instead of bytes.
+//! 3. Proofs are output as their raw bytes, without the 4-byte length prefix
+//!    of their borsh encoding. Decoded `Any` values also have their `raw` bytes.
+//!    Integers in memos follow the `integers` option: the `timeout` of a forward
+//!    and the `gasLimit` of a callback always, other JSON integers above 2^53.
+//!    Proto3 output has a `schemaVersion`, and the upgrade sequences of channels.
+//! 4. The amounts of Namada transfers are `{ amount, denom }`, with the amount
+//!    in base units, instead of a display string without the denomination.
+//! 5. Proto3 output expands the nested `Any` values of the Tendermint light client
+//!    types, instead of outputting their value as base64.
+//! 6. The `ibcDenom` and `namadaToken` of the token in a received packet are those
+//!    of the token on the receiving chain, instead of the sending chain.
+
+/// Current version of the output schema.
+pub const VERSION: u32 = 6;
+
+/// The fields of one type of decoded message.
+#[derive(Clone, Copy, Debug)]
+pub struct Message {
+    /// Value of the `type` field.
+    pub name: &'static str,
+    /// Keys and TypeScript types of the remaining fields.
+    pub fields: &'static [(&'static str, &'static str)],
+}
+
+impl Message {
+    /// Get the schema of a message type.
+    pub fn get (name: &str) -> Option<&'static Self> {
+        MESSAGES.iter().find(|message|message.name == name)
+    }
+
+    /// Name of the TypeScript interface for this message type,
+    /// e.g. `EnvelopeClientUpdate` for `envelope.client.update`.
+    pub fn interface (&self) -> String {
+        self.name.split(['.', '_']).map(|word|{
+            let mut chars = word.chars();
+            chars.next().map(|first|first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        }).collect::<Vec<String>>().concat()
+    }
+
+    /// All the keys of a message of this type, in output order.
+    pub fn keys (&self) -> Vec<&'static str> {
+        let mut keys = vec!["type", "schemaVersion"];
+        keys.extend(self.fields.iter().map(|(key, _)|*key));
+        keys
+    }
+}
+
+/// Every type of decoded message.
+pub const MESSAGES: &[Message] = &[
+    Message { name: "envelope.client.create", fields: &[
+        ("clientState",    "Any"),
+        ("consensusState", "Any"),
+        ("signer",         "string"),
+    ] },
+    Message { name: "envelope.client.update", fields: &[
+        ("clientId",      "string"),
+        ("clientMessage", "Any"),
+        ("signer",        "string"),
+    ] },
+    Message { name: "envelope.client.misbehaviour", fields: &[
+        ("clientId",     "string"),
+        ("misbehaviour", "Any"),
+        ("signer",       "string"),
+    ] },
+    Message { name: "envelope.client.upgrade", fields: &[
+        ("clientId",                   "string"),
+        ("upgradedClientState",        "Any"),
+        ("upgradedConsensusState",     "Any"),
+        ("proofUpgradeClient",         "Uint8Array"),
+        ("proofUpgradeConsensusState", "Uint8Array"),
+        ("signer",                     "string"),
+    ] },
+    Message { name: "envelope.client.recover", fields: &[
+        ("subjectClientId",    "string"),
+        ("substituteClientId", "string"),
+        ("signer",             "string"),
+    ] },
+    Message { name: "envelope.connection.open_init", fields: &[
+        ("clientIdOnA",  "string"),
+        ("counterparty", "ConnectionCounterparty"),
+        ("version",      "ConnectionVersion | null"),
+        ("delayPeriod",  "Duration"),
+        ("signer",       "string"),
+    ] },
+    Message { name: "envelope.connection.open_try", fields: &[
+        ("clientIdOnB",               "string"),
+        ("clientStateOfBOnA",         "Any"),
+        ("counterparty",              "ConnectionCounterparty"),
+        ("versionsOnA",               "ConnectionVersion[]"),
+        ("proofConnEndOnA",           "Uint8Array"),
+        ("proofClientStateOfBOnA",    "Uint8Array"),
+        ("proofConsensusStateOfBOnA", "Uint8Array"),
+        ("proofsHeightOnA",           "Height"),
+        ("consensusHeightOfBOnA",     "Height"),
+        ("delayPeriod",               "Duration"),
+        ("signer",                    "string"),
+        ("proofConsensusStateOfB",    "Uint8Array | null"),
+        ("previousConnectionId",      "string"),
+    ] },
+    Message { name: "envelope.connection.open_ack", fields: &[
+        ("connIdOnA",                 "string"),
+        ("connIdOnB",                 "string"),
+        ("clientStateOfAOnB",         "Any"),
+        ("proofConnEndOnB",           "Uint8Array"),
+        ("proofClientStateOfAOnB",    "Uint8Array"),
+        ("proofConsensusStateOfAOnB", "Uint8Array"),
+        ("proofsHeightOnB",           "Height"),
+        ("consensusHeightOfAOnB",     "Height"),
+        ("version",                   "ConnectionVersion"),
+        ("signer",                    "string"),
+        ("proofConsensusStateOfA",    "Uint8Array | null"),
+    ] },
+    Message { name: "envelope.connection.open_confirm", fields: &[
+        ("connIdOnB",       "string"),
+        ("proofConnEndOnA", "Uint8Array"),
+        ("proofHeightOnA",  "Height"),
+        ("signer",          "string"),
+    ] },
+    Message { name: "envelope.channel.open_init", fields: &[
+        ("portIdOnA",         "string"),
+        ("connectionHopsOnA", "string[]"),
+        ("portIdOnB",         "string"),
+        ("ordering",          "Order"),
+        ("signer",            "string"),
+        ("versionProposal",   "string"),
+    ] },
+    Message { name: "envelope.channel.open_try", fields: &[
+        ("portIdOnB",           "string"),
+        ("connectionHopsOnB",   "string[]"),
+        ("portIdOnA",           "string"),
+        ("chanIdOnA",           "string"),
+        ("versionSupportedOnA", "string"),
+        ("proofChanEndOnA",     "Uint8Array"),
+        ("proofHeightOnA",      "Height"),
+        ("ordering",            "Order"),
+        ("signer",              "string"),
+        ("versionProposal",     "string"),
+    ] },
+    Message { name: "envelope.channel.open_ack", fields: &[
+        ("portIdOnA",       "string"),
+        ("chanIdOnA",       "string"),
+        ("chanIdOnB",       "string"),
+        ("versionOnB",      "string"),
+        ("proofChanEndOnB", "Uint8Array"),
+        ("proofHeightOnB",  "Height"),
+        ("signer",          "string"),
+    ] },
+    Message { name: "envelope.channel.open_confirm", fields: &[
+        ("portIdOnB",       "string"),
+        ("chanIdOnB",       "string"),
+        ("proofChanEndOnA", "Uint8Array"),
+        ("proofHeightOnA",  "Height"),
+        ("signer",          "string"),
+    ] },
+    Message { name: "envelope.channel.close_init", fields: &[
+        ("portIdOnA", "string"),
+        ("chanIdOnA", "string"),
+        ("signer",    "string"),
+    ] },
+    Message { name: "envelope.channel.close_confirm", fields: &[
+        ("portIdOnB",       "string"),
+        ("chanIdOnB",       "string"),
+        ("proofChanEndOnA", "Uint8Array"),
+        ("proofHeightOnA",  "Height"),
+        ("signer",          "string"),
+    ] },
+    Message { name: "envelope.packet.recv", fields: &[
+        ("packet",             "Packet"),
+        ("proofCommitmentOnA", "Uint8Array"),
+        ("proofHeightOnA",     "Height"),
+        ("signer",             "string"),
+    ] },
+    Message { name: "envelope.packet.ack", fields: &[
+        ("packet",          "Packet"),
+        ("acknowledgement", "Acknowledgement"),
+        ("proofAckedOnB",   "Uint8Array"),
+        ("proofHeightOnB",  "Height"),
+        ("signer",          "string"),
+    ] },
+    Message { name: "envelope.packet.timeout", fields: &[
+        ("packet",             "Packet"),
+        ("nextSeqRecvOnB",     "Int64"),
+        ("proofUnreceivedOnB", "Uint8Array"),
+        ("proofHeightOnB",     "Height"),
+        ("signer",             "string"),
+    ] },
+    Message { name: "envelope.packet.timeout_on_close", fields: &[
+        ("packet",             "Packet"),
+        ("nextSeqRecvOnB",     "Int64"),
+        ("proofUnreceivedOnB", "Uint8Array"),
+        ("proofCloseOnB",      "Uint8Array"),
+        ("proofHeightOnB",     "Height"),
+        ("signer",             "string"),
+    ] },
+    Message { name: "transfer", fields: &[
+        ("message",  "MsgTransfer"),
+        ("transfer", "NamadaTransfer | null"),
+    ] },
+    Message { name: "nft_transfer", fields: &[
+        ("message",  "MsgNftTransfer"),
+        ("transfer", "NamadaTransfer | null"),
+    ] },
+];
+
+/// TypeScript declarations of the shapes shared between message types.
+const SHARED_TYPES: &str = r#"/** A 64-bit integer: a `bigint`, or a decimal string when decoding with `{ integers: "string" }`. */
+export type Int64 = bigint | string;
+
+export interface DecodeOptions {
+  validatorSets?: "full" | "hash";
+  integers?: "bigint" | "string";
+  format?: "native" | "proto3";
+  lenient?: boolean;
+}
+
+/** A message in canonical proto3 JSON, as output with `{ format: "proto3" }`. */
+export interface Proto3Message {
+  "@type": string;
+  schemaVersion: number;
+  [field: string]: unknown;
+}
+
+export type Proto3Options = DecodeOptions & { format: "proto3" };
+
+/** The output of these depends on the `format` option, so they have an overload per format. */
+export declare namespace Decode {
+  function ibc (source: Uint8Array, options: Proto3Options): Proto3Message;
+  function ibc (source: Uint8Array, options?: DecodeOptions | null): DecodedIbc;
+  function ibcBatch (sources: unknown[], options: Proto3Options): DecodeBatchResult<Proto3Message>[];
+  function ibcBatch (sources: unknown[], options?: DecodeOptions | null): DecodeBatchResult[];
+  function tx (source: Uint8Array, options: Proto3Options): DecodedTxEntry<Proto3Message>[];
+  function tx (source: Uint8Array, options?: DecodeOptions | null): DecodedTxEntry[];
+}
+
+/**
+ * `value` is the output of the decoder registered for `typeUrl`, or the raw bytes if there is none,
+ * or a `DecodeFailure` if the decoder failed with `{ lenient: true }`.
+ * `raw` is present when there is a decoder, and is what `Encode.ibc` re-encodes.
+ */
+export interface Any {
+  typeUrl: string;
+  value: unknown;
+  raw?: Uint8Array;
+}
+
+export interface Height {
+  revisionNumber: Int64;
+  revisionHeight: Int64;
+}
+
+export interface Duration {
+  nanos: Int64;
+  iso: string;
+}
+
+export type TimeoutHeight = "never" | Height;
+
+export type TimeoutTimestamp = "never" | { nanos: Int64; iso: string };
+
+export type Order = "ORDER_NONE_UNSPECIFIED" | "ORDER_UNORDERED" | "ORDER_ORDERED";
+
+export interface ConnectionCounterparty {
+  clientId: string;
+  connectionId: string | null;
+  prefix: Uint8Array;
+}
+
+export interface ConnectionVersion {
+  identifier: string;
+  features: string[];
+}
+
+export interface Packet {
+  seqOnA: Int64;
+  portIdOnA: string;
+  chanIdOnA: string;
+  portIdOnB: string;
+  chanIdOnB: string;
+  data: Uint8Array;
+  packetData: PacketData | DecodeFailure;
+  timeoutHeightOnB: TimeoutHeight;
+  timeoutTimestampOnB: TimeoutTimestamp;
+}
+
+export type PacketData =
+  | { kind: "ics20"; denom: string; amount: string; token: PrefixedCoin; sender: string; receiver: string; memo: Memo }
+  | ({ kind: "ics721" } & NftPacketData)
+  | { kind: "text"; text: string }
+  | { kind: "bytes"; bytes: Uint8Array };
+
+export interface Acknowledgement {
+  status: "success" | "error" | "unknown";
+  result: Uint8Array | null;
+  error: string | null;
+  app: { kind: "ics20" | "ics721"; success: boolean } | null;
+  raw: Uint8Array;
+}
+
+/** `ibcDenom` and `namadaToken` are those of the token on the chain that processes the message, the receiving one for `envelope.packet.recv`. */
+export interface PrefixedCoin {
+  denom: string;
+  amount: string;
+  baseDenom: string;
+  trace: { portId: string; channelId: string | null }[];
+  ibcDenom: string | null;
+  namadaToken: string | null;
+}
+
+export type Memo =
+  | { kind: "empty" | "text"; text: string }
+  | { kind: "namada-shielding"; text: string; shielding: ShieldingData }
+  | JsonMemo;
+
+export interface ShieldingData {
+  maspTxId: string;
+  shieldedSectionHash: string;
+  spends: number;
+  converts: number;
+  outputs: number;
+  transparentInputs: number;
+  transparentOutputs: number;
+}
+
+export interface JsonMemo {
+  kind: "forward" | "wasm-hook" | "callback" | "json";
+  text: string;
+  json: Record<string, unknown>;
+  forward?: {
+    receiver: unknown;
+    port: unknown;
+    channel: unknown;
+    timeout: unknown;
+    retries: unknown;
+    next: Memo | null;
+  };
+  wasm?: { contract: unknown; msg: unknown };
+  srcCallback?: { address: unknown; gasLimit: unknown };
+  destCallback?: { address: unknown; gasLimit: unknown };
+}
+
+export interface TransferPacketData {
+  token: PrefixedCoin;
+  sender: string;
+  receiver: string;
+  memo: Memo;
+}
+
+export interface NftPacketData {
+  classId: { tracePath: string; baseClassId: string };
+  classUri: string | null;
+  classData: string | null;
+  tokenIds: string[];
+  tokenUris: string[] | null;
+  tokenData: string[] | null;
+  sender: string;
+  receiver: string;
+  memo: Memo | null;
+}
+
+export interface MsgTransfer {
+  portIdOnA: string;
+  chanIdOnA: string;
+  packetData: TransferPacketData;
+  timeoutHeightOnB: TimeoutHeight;
+  timeoutTimestampOnB: TimeoutTimestamp;
+}
+
+export interface MsgNftTransfer {
+  portIdOnA: string;
+  chanIdOnA: string;
+  packetData: NftPacketData;
+  timeoutHeightOnB: TimeoutHeight;
+  timeoutTimestampOnB: TimeoutTimestamp;
+}
+
+/** Amount in base units, as a decimal string, and its number of decimal places. */
+export interface DenominatedAmount {
+  amount: string;
+  denom: number;
+}
+
+export interface NamadaTransfer {
+  sources: { owner: string; token: string; amount: DenominatedAmount }[];
+  targets: { owner: string; token: string; amount: DenominatedAmount }[];
+  shieldedSectionHash: string | null;
+}
+
+/** An IBC inner transaction of a `Tx`; a failure to decode it doesn't fail the others. */
+export type DecodedTxEntry<T = DecodedIbc> = { index: number; codeHash: string; dataHash: string } & (
+  | { ok: true; decoded: T }
+  | { ok: false; error: IbcDecodeError }
+);
+
+/** Error thrown, or returned in batches, when a message can't be decoded. */
+export interface IbcDecodeError extends Error {
+  kind: "borsh" | "protobuf" | "unknown-type-url" | "invalid-identifier" | "conversion" | "invalid-input";
+  stage: "tx" | "envelope" | "any" | "packet-data" | "memo" | "input";
+  typeUrl: string | null;
+  path: string;
+  offset: number | null;
+}
+
+/** Output with `{ lenient: true }` in place of a nested value that failed to decode. */
+export interface DecodeFailure {
+  error: IbcDecodeError;
+  raw: Uint8Array;
+}
+
+export type DecodeBatchResult<T = DecodedIbc> =
+  | { ok: true; value: T }
+  | { ok: false; error: IbcDecodeError };
+"#;
+
+/// Generate the TypeScript declarations of the output: the shared shapes,
+/// one interface per message type, and their discriminated union `DecodedIbc`.
+///
+/// The result is checked in as `src/decoded.d.ts`, and included in the `.d.ts`
+/// that `wasm-pack` generates. Run `just types` to update it after changing the schema.
+pub fn typescript () -> String {
+    let mut ts = String::from(SHARED_TYPES);
+    for message in MESSAGES {
+        ts.push_str(&format!("\nexport interface {} {{\n", message.interface()));
+        ts.push_str(&format!("  type: \"{}\";\n", message.name));
+        ts.push_str(&format!("  schemaVersion: {VERSION};\n"));
+        for (key, ty) in message.fields {
+            ts.push_str(&format!("  {key}: {ty};\n"));
+        }
+        ts.push_str("  warnings?: IbcDecodeError[];\n");
+        ts.push_str("}\n");
+    }
+    let members: Vec<_> = MESSAGES.iter()
+        .map(|message|format!("  | {}", message.interface()))
+        .collect();
+    ts.push_str(&format!("\nexport type DecodedIbc =\n{};\n", members.join("\n")));
+    ts
+}
@@ -0,0 +1,128 @@
+//! The `namada-ibc-decode` command: input detection, exit codes and NDJSON records.
+
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use base64::prelude::*;
+use serde_json::Value as Json;
+use namada_ibc_decoder::{encode, ToJS};
+
+/// Run the command with the given arguments and stdin.
+fn run (args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_namada-ibc-decode"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout_json (output: &Output) -> Json {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// A sample payload, and its expected output.
+fn sample () -> (Vec<u8>, Json) {
+    let sample = common::samples().remove(0);
+    (encode::encode(&sample).unwrap(), sample.to_js().unwrap().to_json())
+}
+
+#[test]
+fn detects_hex_and_base64_payloads () {
+    let (bytes, expected) = sample();
+    for payload in [
+        hex::encode(&bytes),
+        format!("0x{}", hex::encode(&bytes)),
+        BASE64_STANDARD.encode(&bytes),
+    ] {
+        let output = run(&[&payload], b"");
+        assert_eq!(output.status.code(), Some(0), "{payload}");
+        assert_eq!(stdout_json(&output), expected, "{payload}");
+    }
+}
+
+#[test]
+fn reads_raw_bytes_from_a_file_and_stdin () {
+    let (bytes, expected) = sample();
+    let path = std::env::temp_dir().join(format!("namada-ibc-decode-{}.bin", std::process::id()));
+    std::fs::write(&path, &bytes).unwrap();
+    let output = run(&["--raw", "--file", path.to_str().unwrap()], b"");
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output), expected);
+    let output = run(&["--raw"], &bytes);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout_json(&output), expected);
+}
+
+#[test]
+fn exit_codes () {
+    // Valid hex that isn't an IBC message.
+    assert_eq!(run(&["--hex", "deadbeef"], b"").status.code(), Some(1));
+    // Invalid input or arguments.
+    assert_eq!(run(&["--hex", "xyz"], b"").status.code(), Some(2));
+    assert_eq!(run(&["--base64", "!!!"], b"").status.code(), Some(2));
+    assert_eq!(run(&["--unknown"], b"").status.code(), Some(2));
+    assert_eq!(run(&["--format", "yaml"], b"").status.code(), Some(2));
+    assert_eq!(run(&["--file", "/nonexistent", "00"], b"").status.code(), Some(2));
+}
+
+#[test]
+fn validator_sets_flag () {
+    // The client update, with a Tendermint header.
+    let update = common::tendermint::samples().remove(1);
+    let payload = hex::encode(encode::encode(&update).unwrap());
+    let hash = common::tendermint::validators().hash().to_js().unwrap().to_json();
+    let output = run(&["--validator-sets", "hash", &payload], b"");
+    assert_eq!(output.status.code(), Some(0));
+    let header = &stdout_json(&output)["clientMessage"]["value"];
+    assert_eq!(header["validatorSet"], serde_json::json!({ "hash": hash }));
+    assert_eq!(header["trustedNextValidatorSet"], serde_json::json!({ "hash": hash }));
+    let output = run(&["--validator-sets", "full", &payload], b"");
+    assert_eq!(output.status.code(), Some(0));
+    let header = &stdout_json(&output)["clientMessage"]["value"];
+    assert_eq!(header["validatorSet"]["hash"], hash);
+    assert_eq!(header["validatorSet"]["validators"].as_array().map(Vec::len), Some(2));
+    assert!(header["validatorSet"]["proposer"].is_object());
+}
+
+#[test]
+fn ndjson_records () {
+    let (bytes, expected) = sample();
+    let input = [
+        serde_json::json!({ "txHash": "A", "blockHeight": 1, "sectionIndex": 2, "data": hex::encode(&bytes) }).to_string(),
+        serde_json::json!({ "txHash": "B", "data": BASE64_STANDARD.encode(&bytes) }).to_string(),
+        String::new(),
+        serde_json::json!({ "txHash": "C", "data": "deadbeef" }).to_string(),
+        serde_json::json!({ "txHash": "D" }).to_string(),
+        "not json".to_string(),
+    ].join("\n");
+    let output = run(&["--ndjson"], input.as_bytes());
+    assert_eq!(output.status.code(), Some(0));
+    let records: Vec<Json> = std::str::from_utf8(&output.stdout).unwrap()
+        .lines()
+        .map(|line|serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 5);
+
+    assert_eq!(records[0], serde_json::json!({
+        "txHash": "A", "blockHeight": 1, "sectionIndex": 2, "decoded": expected,
+    }));
+    assert_eq!(records[1]["decoded"], expected);
+
+    assert_eq!(records[2]["txHash"], "C");
+    assert!(records[2].get("decoded").is_none());
+    for key in ["message", "kind", "stage", "typeUrl", "path", "offset"] {
+        assert!(records[2]["error"].get(key).is_some(), "no {key} in {}", records[2]);
+    }
+
+    // Invalid lines are numbered from 1, counting the empty line.
+    assert_eq!(records[3]["line"], 5);
+    assert_eq!(records[3]["error"]["kind"], "invalid-input");
+    assert_eq!(records[3]["error"]["stage"], "input");
+    assert_eq!(records[4]["line"], 6);
+}
@@ -0,0 +1,261 @@
+//! Sample messages, one for each variant of `DecodedIbc`.
+
+// Some of the message fields are deprecated upstream, but still have to be filled in.
+#![allow(deprecated)]
+
+pub mod namada;
+pub mod tendermint;
+
+use std::time::Duration;
+use namada_ibc_decoder::DecodedIbc;
+use namada_sdk::ibc::{
+    MsgTransfer as IbcMsgTransfer,
+    MsgNftTransfer as IbcMsgNftTransfer,
+    apps::{transfer, nft_transfer},
+    core::{
+        client::{context::types::msgs::*, types::Height},
+        connection::types::{msgs::*, Counterparty, version::Version as ConnectionVersion},
+        channel::types::{
+            msgs::*,
+            Version as ChannelVersion,
+            acknowledgement::Acknowledgement,
+            channel::Order,
+            packet::Packet,
+            timeout::{TimeoutHeight, TimeoutTimestamp},
+        },
+        commitment_types::commitment::{CommitmentPrefix, CommitmentProofBytes},
+        host::types::identifiers::{ClientId, ConnectionId, ChannelId, PortId, Sequence},
+    },
+    primitives::{Signer, proto::Any},
+};
+
+pub fn signer () -> Signer {
+    Signer::from("tnam1qrxsru5rdu4he400xny6p779fcw7xuftsgjnmzup".to_string())
+}
+
+pub fn client_id () -> ClientId {
+    "07-tendermint-0".parse().unwrap()
+}
+
+pub fn height () -> Height {
+    Height::new(1, 100).unwrap()
+}
+
+pub fn proof () -> CommitmentProofBytes {
+    CommitmentProofBytes::try_from(vec![1, 2, 3]).unwrap()
+}
+
+pub fn any () -> Any {
+    Any { type_url: "/test.v1.Sample".to_string(), value: vec![4, 5, 6] }
+}
+
+pub fn counterparty () -> Counterparty {
+    Counterparty::new(
+        client_id(),
+        Some(ConnectionId::new(1)),
+        CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap(),
+    )
+}
+
+pub fn transfer_packet_data () -> transfer::types::packet::PacketData {
+    transfer::types::packet::PacketData {
+        token:    "1000transfer/channel-0/uosmo".parse().unwrap(),
+        sender:   signer(),
+        receiver: Signer::from("osmo1ehcg3lmpt0d4m9ygqvc6c3wz8t4zu8jrgezzwv".to_string()),
+        memo:     transfer::types::Memo::from(String::new()),
+    }
+}
+
+pub fn packet () -> Packet {
+    Packet {
+        seq_on_a:               Sequence::from(1),
+        port_id_on_a:           PortId::transfer(),
+        chan_id_on_a:           ChannelId::new(0),
+        port_id_on_b:           PortId::transfer(),
+        chan_id_on_b:           ChannelId::new(1),
+        data:                   serde_json::to_vec(&transfer_packet_data()).unwrap(),
+        timeout_height_on_b:    TimeoutHeight::At(height()),
+        timeout_timestamp_on_b: TimeoutTimestamp::Never,
+    }
+}
+
+/// One sample of every message variant.
+pub fn samples () -> Vec<DecodedIbc> {
+    vec![
+        DecodedIbc::Client(ClientMsg::CreateClient(MsgCreateClient {
+            client_state:    any(),
+            consensus_state: any(),
+            signer:          signer(),
+        })),
+        DecodedIbc::Client(ClientMsg::UpdateClient(MsgUpdateClient {
+            client_id:      client_id(),
+            client_message: any(),
+            signer:         signer(),
+        })),
+        DecodedIbc::Client(ClientMsg::Misbehaviour(MsgSubmitMisbehaviour {
+            client_id:    client_id(),
+            misbehaviour: any(),
+            signer:       signer(),
+        })),
+        DecodedIbc::Client(ClientMsg::UpgradeClient(MsgUpgradeClient {
+            client_id:                     client_id(),
+            upgraded_client_state:         any(),
+            upgraded_consensus_state:      any(),
+            proof_upgrade_client:          proof(),
+            proof_upgrade_consensus_state: proof(),
+            signer:                        signer(),
+        })),
+        DecodedIbc::Client(ClientMsg::RecoverClient(MsgRecoverClient {
+            subject_client_id:    client_id(),
+            substitute_client_id: "07-tendermint-1".parse().unwrap(),
+            signer:               signer(),
+        })),
+        DecodedIbc::Connection(ConnectionMsg::OpenInit(MsgConnectionOpenInit {
+            client_id_on_a: client_id(),
+            counterparty:   counterparty(),
+            version:        ConnectionVersion::compatibles().into_iter().next(),
+            delay_period:   Duration::from_secs(86400),
+            signer:         signer(),
+        })),
+        DecodedIbc::Connection(ConnectionMsg::OpenTry(MsgConnectionOpenTry {
+            client_id_on_b:                  client_id(),
+            client_state_of_b_on_a:          any(),
+            counterparty:                    counterparty(),
+            versions_on_a:                   ConnectionVersion::compatibles(),
+            proof_conn_end_on_a:             proof(),
+            proof_client_state_of_b_on_a:    proof(),
+            proof_consensus_state_of_b_on_a: proof(),
+            proofs_height_on_a:              height(),
+            consensus_height_of_b_on_a:      height(),
+            delay_period:                    Duration::from_millis(1500),
+            signer:                          signer(),
+            proof_consensus_state_of_b:      None,
+            previous_connection_id:          String::new(),
+        })),
+        DecodedIbc::Connection(ConnectionMsg::OpenAck(MsgConnectionOpenAck {
+            conn_id_on_a:                    ConnectionId::new(0),
+            conn_id_on_b:                    ConnectionId::new(1),
+            client_state_of_a_on_b:          any(),
+            proof_conn_end_on_b:             proof(),
+            proof_client_state_of_a_on_b:    proof(),
+            proof_consensus_state_of_a_on_b: proof(),
+            proofs_height_on_b:              height(),
+            consensus_height_of_a_on_b:      height(),
+            version:                         ConnectionVersion::compatibles().remove(0),
+            signer:                          signer(),
+            proof_consensus_state_of_a:      None,
+        })),
+        DecodedIbc::Connection(ConnectionMsg::OpenConfirm(MsgConnectionOpenConfirm {
+            conn_id_on_b:        ConnectionId::new(1),
+            proof_conn_end_on_a: proof(),
+            proof_height_on_a:   height(),
+            signer:              signer(),
+        })),
+        DecodedIbc::Channel(ChannelMsg::OpenInit(MsgChannelOpenInit {
+            port_id_on_a:         PortId::transfer(),
+            connection_hops_on_a: vec![ConnectionId::new(0)],
+            port_id_on_b:         PortId::transfer(),
+            ordering:             Order::Unordered,
+            signer:               signer(),
+            version_proposal:     ChannelVersion::new("ics20-1".to_string()),
+        })),
+        DecodedIbc::Channel(ChannelMsg::OpenTry(MsgChannelOpenTry {
+            port_id_on_b:           PortId::transfer(),
+            connection_hops_on_b:   vec![ConnectionId::new(1)],
+            port_id_on_a:           PortId::transfer(),
+            chan_id_on_a:           ChannelId::new(0),
+            version_supported_on_a: ChannelVersion::new("ics20-1".to_string()),
+            proof_chan_end_on_a:    proof(),
+            proof_height_on_a:      height(),
+            ordering:               Order::Unordered,
+            signer:                 signer(),
+            version_proposal:       ChannelVersion::new("ics20-1".to_string()),
+        })),
+        DecodedIbc::Channel(ChannelMsg::OpenAck(MsgChannelOpenAck {
+            port_id_on_a:        PortId::transfer(),
+            chan_id_on_a:        ChannelId::new(0),
+            chan_id_on_b:        ChannelId::new(1),
+            version_on_b:        ChannelVersion::new("ics20-1".to_string()),
+            proof_chan_end_on_b: proof(),
+            proof_height_on_b:   height(),
+            signer:              signer(),
+        })),
+        DecodedIbc::Channel(ChannelMsg::OpenConfirm(MsgChannelOpenConfirm {
+            port_id_on_b:        PortId::transfer(),
+            chan_id_on_b:        ChannelId::new(1),
+            proof_chan_end_on_a: proof(),
+            proof_height_on_a:   height(),
+            signer:              signer(),
+        })),
+        DecodedIbc::Channel(ChannelMsg::CloseInit(MsgChannelCloseInit {
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::new(0),
+            signer:       signer(),
+        })),
+        DecodedIbc::Channel(ChannelMsg::CloseConfirm(MsgChannelCloseConfirm {
+            port_id_on_b:        PortId::transfer(),
+            chan_id_on_b:        ChannelId::new(1),
+            proof_chan_end_on_a: proof(),
+            proof_height_on_a:   height(),
+            signer:              signer(),
+        })),
+        DecodedIbc::Packet(PacketMsg::Recv(MsgRecvPacket {
+            packet:                packet(),
+            proof_commitment_on_a: proof(),
+            proof_height_on_a:     height(),
+            signer:                signer(),
+        })),
+        DecodedIbc::Packet(PacketMsg::Ack(MsgAcknowledgement {
+            packet:            packet(),
+            acknowledgement:   Acknowledgement::try_from(br#"{"result":"AQ=="}"#.to_vec()).unwrap(),
+            proof_acked_on_b:  proof(),
+            proof_height_on_b: height(),
+            signer:            signer(),
+        })),
+        DecodedIbc::Packet(PacketMsg::Timeout(MsgTimeout {
+            packet:                packet(),
+            next_seq_recv_on_b:    Sequence::from(2),
+            proof_unreceived_on_b: proof(),
+            proof_height_on_b:     height(),
+            signer:                signer(),
+        })),
+        DecodedIbc::Packet(PacketMsg::TimeoutOnClose(MsgTimeoutOnClose {
+            packet:                packet(),
+            next_seq_recv_on_b:    Sequence::from(2),
+            proof_unreceived_on_b: proof(),
+            proof_close_on_b:      proof(),
+            proof_height_on_b:     height(),
+            signer:                signer(),
+        })),
+        DecodedIbc::Transfer(Box::new(IbcMsgTransfer {
+            message: transfer::types::msgs::transfer::MsgTransfer {
+                port_id_on_a:           PortId::transfer(),
+                chan_id_on_a:           ChannelId::new(0),
+                packet_data:            transfer_packet_data(),
+                timeout_height_on_b:    TimeoutHeight::At(height()),
+                timeout_timestamp_on_b: TimeoutTimestamp::Never,
+            },
+            transfer: None,
+        })),
+        DecodedIbc::NftTransfer(IbcMsgNftTransfer {
+            message: nft_transfer::types::msgs::transfer::MsgTransfer {
+                port_id_on_a:           "nft-transfer".parse().unwrap(),
+                chan_id_on_a:           ChannelId::new(2),
+                packet_data:            nft_transfer::types::packet::PacketData {
+                    class_id:   "nft-transfer/channel-2/class".parse().unwrap(),
+                    class_uri:  None,
+                    class_data: None,
+                    token_ids:  nft_transfer::types::TokenIds(vec!["token-1".parse().unwrap()]),
+                    token_uris: None,
+                    token_data: None,
+                    sender:     signer(),
+                    receiver:   signer(),
+                    memo:       None,
+                },
+                timeout_height_on_b:    TimeoutHeight::Never,
+                timeout_timestamp_on_b: TimeoutTimestamp::Never,
+            },
+            transfer: None,
+        }),
+    ]
+}
@@ -0,0 +1,40 @@
+//! Namada transfers, attached to IBC transfers the way Namada sends them.
+
+// Not every test uses every sample.
+#![allow(dead_code)]
+
+use namada_ibc_decoder::DecodedIbc;
+use namada_sdk::{
+    address::{Address, MASP},
+    hash::Hash,
+    token::{Account, Amount, DenominatedAmount, Denomination, Transfer},
+};
+
+/// NAM on Namada mainnet.
+pub const NAM: &str = "tnam1q9gr66cvu4hrzm0sd5kmlnjje82gs3xlfg3v6nu7";
+
+/// An amount of `units` base units with `denom` decimal places.
+pub fn amount (units: u64, denom: u8) -> DenominatedAmount {
+    DenominatedAmount::new(Amount::from_u64(units), Denomination(denom))
+}
+
+/// A shielding transfer of 1 NAM, denominated in micro-NAM, from the sender of the samples.
+pub fn transfer () -> Transfer {
+    let account = |owner: Address| Account { owner, token: NAM.parse().unwrap() };
+    Transfer {
+        sources:               [(account(super::signer().as_ref().parse().unwrap()), amount(1_000_000, 6))].into(),
+        targets:               [(account(MASP), amount(1_000_000, 6))].into(),
+        shielded_section_hash: Some(Hash([7; 32])),
+    }
+}
+
+/// The samples of IBC transfers, with [`transfer`] attached.
+pub fn samples () -> Vec<DecodedIbc> {
+    super::samples().into_iter().filter_map(|sample|match sample {
+        DecodedIbc::Transfer(mut message) => {
+            message.transfer = Some(transfer());
+            Some(DecodedIbc::Transfer(message))
+        },
+        _ => None,
+    }).collect()
+}
@@ -0,0 +1,159 @@
+//! Tendermint light client values, packed in `Any`s the way relayers send them.
+
+// Not every test uses every sample.
+#![allow(dead_code)]
+
+use std::time::Duration;
+use namada_ibc_decoder::DecodedIbc;
+use namada_sdk::ibc::{
+    clients::tendermint::types::{
+        AllowUpdate, ClientState, ConsensusState, Header, Misbehaviour, TrustThreshold,
+    },
+    core::{
+        client::{context::types::msgs::ClientMsg, types::Height},
+        connection::types::msgs::ConnectionMsg,
+        commitment_types::{commitment::CommitmentRoot, specs::ProofSpecs},
+        host::types::identifiers::ChainId,
+    },
+    primitives::{proto::Any, ToProto},
+};
+use namada_sdk::tendermint::{
+    self, account, block::{self, parts, signed_header::SignedHeader, Commit, Round},
+    validator, vote, AppHash, Hash, PublicKey, Time,
+};
+
+fn hash (byte: u8) -> Hash {
+    Hash::Sha256([byte; 32])
+}
+
+fn time (seconds: i64) -> Time {
+    Time::from_unix_timestamp(seconds, 0).unwrap()
+}
+
+/// A block header at the given height and time. Different `app_hash`es
+/// give different blocks for the same height.
+pub fn block_header (height: u64, seconds: i64, app_hash: u8) -> block::Header {
+    block::Header {
+        version:              block::header::Version { block: 11, app: 0 },
+        chain_id:             "namada-test".parse::<tendermint::chain::Id>().unwrap(),
+        height:               block::Height::try_from(height).unwrap(),
+        time:                 time(seconds),
+        last_block_id:        Some(block::Id {
+            hash:             hash(1),
+            part_set_header:  parts::Header::new(1, hash(2)).unwrap(),
+        }),
+        last_commit_hash:     Some(hash(3)),
+        data_hash:            Some(hash(4)),
+        validators_hash:      hash(5),
+        next_validators_hash: hash(5),
+        consensus_hash:       hash(6),
+        app_hash:             AppHash::try_from(vec![app_hash; 32]).unwrap(),
+        last_results_hash:    Some(hash(7)),
+        evidence_hash:        Some(hash(8)),
+        proposer_address:     account::Id::new([9; 20]),
+    }
+}
+
+/// Two validators with different voting powers, the second of which is the proposer.
+pub fn validators () -> validator::Set {
+    // The public keys of the first two test vectors of RFC 8032.
+    let validator = |key: &str, power: u32| validator::Info::new(
+        PublicKey::from_raw_ed25519(&hex::decode(key).unwrap()).unwrap(),
+        vote::Power::from(power),
+    );
+    let first = validator("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a", 20);
+    let second = validator("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c", 10);
+    validator::Set::new(vec![first, second.clone()], Some(second))
+}
+
+/// A light client header for the given block header.
+pub fn header (block_header: block::Header) -> Header {
+    let commit = Commit {
+        height:     block_header.height,
+        round:      Round::default(),
+        block_id:   block::Id {
+            hash:            block_header.hash(),
+            part_set_header: parts::Header::new(1, hash(2)).unwrap(),
+        },
+        signatures: vec![],
+    };
+    Header {
+        signed_header:              SignedHeader::new(block_header, commit).unwrap(),
+        validator_set:              validators(),
+        trusted_height:             Height::new(0, 1).unwrap(),
+        trusted_next_validator_set: validators(),
+    }
+}
+
+pub fn client_state () -> ClientState {
+    ClientState::new(
+        ChainId::new("namada-test").unwrap(),
+        TrustThreshold::ONE_THIRD,
+        Duration::from_secs(14 * 86400),
+        Duration::from_secs(21 * 86400),
+        Duration::from_secs(20),
+        Height::new(0, 100).unwrap(),
+        ProofSpecs::cosmos(),
+        vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+        AllowUpdate { after_expiry: true, after_misbehaviour: true },
+    ).unwrap()
+}
+
+pub fn consensus_state () -> ConsensusState {
+    ConsensusState::new(CommitmentRoot::from_bytes(&[10; 32]), time(1_700_000_000), hash(5))
+}
+
+pub fn client_state_any () -> Any {
+    client_state().to_any()
+}
+
+pub fn consensus_state_any () -> Any {
+    consensus_state().to_any()
+}
+
+pub fn header_any () -> Any {
+    header(block_header(100, 1_700_000_000, 0)).to_any()
+}
+
+/// Two conflicting headers for the same height.
+pub fn misbehaviour_any () -> Any {
+    Misbehaviour::new(
+        super::client_id(),
+        header(block_header(100, 1_700_000_000, 0)),
+        header(block_header(100, 1_700_000_000, 1)),
+    ).to_any()
+}
+
+/// The samples of the messages that contain light client values,
+/// with Tendermint values instead of opaque ones.
+pub fn samples () -> Vec<DecodedIbc> {
+    super::samples().into_iter().filter_map(|sample|Some(match sample {
+        DecodedIbc::Client(ClientMsg::CreateClient(mut message)) => {
+            message.client_state    = client_state_any();
+            message.consensus_state = consensus_state_any();
+            DecodedIbc::Client(ClientMsg::CreateClient(message))
+        },
+        DecodedIbc::Client(ClientMsg::UpdateClient(mut message)) => {
+            message.client_message = header_any();
+            DecodedIbc::Client(ClientMsg::UpdateClient(message))
+        },
+        DecodedIbc::Client(ClientMsg::Misbehaviour(mut message)) => {
+            message.misbehaviour = misbehaviour_any();
+            DecodedIbc::Client(ClientMsg::Misbehaviour(message))
+        },
+        DecodedIbc::Client(ClientMsg::UpgradeClient(mut message)) => {
+            message.upgraded_client_state    = client_state_any();
+            message.upgraded_consensus_state = consensus_state_any();
+            DecodedIbc::Client(ClientMsg::UpgradeClient(message))
+        },
+        DecodedIbc::Connection(ConnectionMsg::OpenTry(mut message)) => {
+            message.client_state_of_b_on_a = client_state_any();
+            DecodedIbc::Connection(ConnectionMsg::OpenTry(message))
+        },
+        DecodedIbc::Connection(ConnectionMsg::OpenAck(mut message)) => {
+            message.client_state_of_a_on_b = client_state_any();
+            DecodedIbc::Connection(ConnectionMsg::OpenAck(message))
+        },
+        _ => return None,
+    })).collect()
+}
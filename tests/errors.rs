@@ -0,0 +1,130 @@
+//! Decode errors say what kind of failure they are, and where it happened.
+
+mod common;
+
+use namada_ibc_decoder::{decode, decode_ibc, encode, DecodedIbc, ErrorKind, Options, Stage, ToJS, Value};
+use namada_sdk::ibc::{
+    core::client::context::types::msgs::{ClientMsg, MsgCreateClient},
+    primitives::proto::Any,
+};
+
+#[test]
+fn unknown_type_url () {
+    use prost::Message;
+    let any = namada_sdk::ibc::primitives::proto::Any {
+        type_url: "/test.v1.Unknown".into(),
+        value:    vec![],
+    };
+    let error = decode(&any.encode_to_vec()).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::UnknownTypeUrl);
+    assert_eq!(error.stage, Stage::Envelope);
+    assert_eq!(error.type_url.as_deref(), Some("/test.v1.Unknown"));
+}
+
+#[test]
+fn invalid_protobuf () {
+    use prost::Message;
+    let any = namada_sdk::ibc::primitives::proto::Any {
+        type_url: "/ibc.core.client.v1.MsgCreateClient".into(),
+        value:    vec![0xff, 0xff],
+    };
+    let error = decode(&any.encode_to_vec()).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Protobuf);
+    assert_eq!(error.stage, Stage::Envelope);
+}
+
+#[test]
+fn invalid_identifier_in_message () {
+    use prost::{Message, Name};
+    use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+    let message = MsgUpdateClient {
+        client_id:      "not a client id!".into(),
+        client_message: Some(common::any()),
+        signer:         common::signer().to_string(),
+    };
+    let any = namada_sdk::ibc::primitives::proto::Any {
+        type_url: MsgUpdateClient::type_url(),
+        value:    message.encode_to_vec(),
+    };
+    let error = decode(&any.encode_to_vec()).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidIdentifier, "{error}");
+    assert_eq!(error.stage, Stage::Envelope);
+}
+
+#[test]
+fn invalid_protobuf_in_any () {
+    let message = DecodedIbc::Client(ClientMsg::CreateClient(MsgCreateClient {
+        client_state:    Any { type_url: common::tendermint::client_state_any().type_url, value: vec![0xff] },
+        consensus_state: common::tendermint::consensus_state_any(),
+        signer:          common::signer(),
+    }));
+    let error = decode_ibc(&encode::encode(&message).unwrap()).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Protobuf);
+    assert_eq!(error.stage, Stage::Any);
+    assert_eq!(error.path, ["clientState", "value"]);
+}
+
+#[test]
+fn truncated_borsh_has_offset () {
+    let mut message = common::samples().into_iter().find_map(|sample|match sample {
+        DecodedIbc::Transfer(message) => Some(message),
+        _ => None,
+    }).unwrap();
+    message.transfer = Some(namada_sdk::token::Transfer {
+        sources:               Default::default(),
+        targets:               Default::default(),
+        shielded_section_hash: None,
+    });
+    let bytes = encode::encode(&DecodedIbc::Transfer(message)).unwrap();
+    let truncated = &bytes[..bytes.len() / 2];
+    let error = decode(truncated).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Borsh);
+    assert!(error.offset.is_some_and(|offset|offset <= truncated.len()), "{error}");
+}
+
+#[test]
+fn invalid_identifier_has_path () {
+    let sample = common::samples().into_iter()
+        .map(|sample|sample.to_js().unwrap())
+        .find(|output|matches!(output, Value::Object(o) if o.get("clientId").is_some()))
+        .unwrap();
+    let Value::Object(mut object) = sample else { unreachable!() };
+    object.set("clientId", Value::from("not a client id!"));
+    let error = encode::from_value(&Value::from(object)).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidIdentifier);
+    assert_eq!(error.path, ["clientId"]);
+}
+
+#[test]
+fn lenient_mode_keeps_the_message () {
+    use namada_sdk::ibc::core::channel::types::{msgs::{MsgRecvPacket, PacketMsg}, packet::Packet};
+    let message = DecodedIbc::Packet(PacketMsg::Recv(MsgRecvPacket {
+        packet: Packet { data: b"not json".to_vec(), ..common::packet() },
+        proof_commitment_on_a: common::proof(),
+        proof_height_on_a:     common::height(),
+        signer:                common::signer(),
+    }));
+    let bytes = encode::encode(&message).unwrap();
+    let error = decode_ibc(&bytes).unwrap_err();
+    assert_eq!(error.stage, Stage::PacketData);
+    assert_eq!(error.path, ["packet", "packetData"]);
+    let lenient = Options { lenient: true, ..Options::default() };
+    let Value::Object(output) = lenient.scope(||decode_ibc(&bytes)).unwrap() else { panic!() };
+    let Some(Value::Object(packet)) = output.get("packet") else { panic!() };
+    let Some(Value::Object(packet_data)) = packet.get("packetData") else { panic!() };
+    assert_eq!(packet_data.get("raw"), Some(&Value::from(&b"not json"[..])));
+    assert_eq!(output.get("warnings"), Some(&Value::Array(vec![Value::Error(Box::new(error))])));
+}
+
+#[test]
+fn only_recovered_failures_are_warnings () {
+    use namada_ibc_decoder::{registry, DecodeError};
+    // A decoder can output errors as values, like a JS decoder that returns an `Error`.
+    registry::register("/test.v1.Sample", std::rc::Rc::new(|_: &Any|Ok(Value::Error(Box::new(
+        DecodeError::conversion(Stage::Any, "not a warning")
+    )))));
+    let bytes = encode::encode(&common::samples().remove(0)).unwrap();
+    let lenient = Options { lenient: true, ..Options::default() };
+    let Value::Object(output) = lenient.scope(||decode_ibc(&bytes)).unwrap() else { panic!() };
+    assert_eq!(output.get("warnings"), Some(&Value::Array(vec![])));
+}
@@ -0,0 +1,96 @@
+//! Classification of transfer memos, and decoding of Namada shielding memos.
+
+use namada_ibc_decoder::{memo::Memo, options::Integers, Object, Options, ToJS, Value};
+
+fn memo (text: &str) -> Object {
+    let Value::Object(memo) = Memo(text).to_js().unwrap() else { panic!() };
+    memo
+}
+
+fn object<'a> (object: &'a Object, key: &str) -> &'a Object {
+    let Some(Value::Object(value)) = object.get(key) else { panic!("no {key} in {object:?}") };
+    value
+}
+
+fn kind (memo: &Object) -> &Value {
+    memo.get("kind").unwrap()
+}
+
+#[test]
+fn empty_and_text () {
+    assert_eq!(kind(&memo("")), &Value::from("empty"));
+    let text = memo("gm");
+    assert_eq!(kind(&text), &Value::from("text"));
+    assert_eq!(text.get("text"), Some(&Value::from("gm")));
+    // JSON that isn't an object is text too.
+    assert_eq!(kind(&memo("[1, 2]")), &Value::from("text"));
+}
+
+#[test]
+fn forward () {
+    let text = r#"{"forward":{"receiver":"osmo1","port":"transfer","channel":"channel-1","retries":2,"next":{"wasm":{"contract":"osmo1c","msg":{}}}}}"#;
+    let memo = memo(text);
+    assert_eq!(kind(&memo), &Value::from("forward"));
+    assert_eq!(memo.get("text"), Some(&Value::from(text)));
+    let forward = object(&memo, "forward");
+    assert_eq!(forward.get("receiver"), Some(&Value::from("osmo1")));
+    assert_eq!(forward.get("channel"), Some(&Value::from("channel-1")));
+    assert_eq!(forward.get("timeout"), Some(&Value::Null));
+    assert_eq!(kind(object(forward, "next")), &Value::from("wasm-hook"));
+}
+
+#[test]
+fn wasm_hook () {
+    let memo = memo(r#"{"wasm":{"contract":"osmo1c","msg":{"swap":{}}}}"#);
+    assert_eq!(kind(&memo), &Value::from("wasm-hook"));
+    let wasm = object(&memo, "wasm");
+    assert_eq!(wasm.get("contract"), Some(&Value::from("osmo1c")));
+    assert!(matches!(wasm.get("msg"), Some(Value::Object(_))));
+}
+
+#[test]
+fn callbacks () {
+    let memo = memo(r#"{"src_callback":{"address":"osmo1c","gas_limit":"100000"},"dest_callback":{"address":"osmo1d"}}"#);
+    assert_eq!(kind(&memo), &Value::from("callback"));
+    assert_eq!(object(&memo, "srcCallback").get("gasLimit"), Some(&Value::from("100000")));
+    assert_eq!(object(&memo, "destCallback").get("address"), Some(&Value::from("osmo1d")));
+}
+
+#[test]
+fn other_json () {
+    let memo = memo(r#"{"note":"hi"}"#);
+    assert_eq!(kind(&memo), &Value::from("json"));
+    assert_eq!(object(&memo, "json").get("note"), Some(&Value::from("hi")));
+    assert_eq!(memo.get("forward"), None);
+}
+
+#[test]
+fn namada_shielding () {
+    use namada_sdk::{ibc::IbcShieldingData, masp_primitives::{
+        consensus::{BlockHeight, BranchId},
+        transaction::{Authorized, TransactionData, TxVersion},
+    }};
+    let masp_tx = TransactionData::<Authorized>::from_parts(
+        TxVersion::MASPv5, BranchId::MASP, 0, BlockHeight::from_u32(0), None, None,
+    ).freeze().unwrap();
+    let txid = masp_tx.txid().to_string();
+    let memo = memo(&IbcShieldingData(masp_tx).to_string());
+    assert_eq!(kind(&memo), &Value::from("namada-shielding"));
+    let shielding = object(&memo, "shielding");
+    assert_eq!(shielding.get("maspTxId"), Some(&Value::from(txid)));
+    assert_eq!(shielding.get("spends"), Some(&Value::from(0u32)));
+    assert_eq!(shielding.get("outputs"), Some(&Value::from(0u32)));
+    assert_eq!(shielding.get("transparentInputs"), Some(&Value::from(0u32)));
+}
+
+#[test]
+fn integers_follow_the_option () {
+    let text = r#"{"forward":{"receiver":"a","port":"transfer","channel":"channel-1","timeout":600000000000},"n":18446744073709551615}"#;
+    let strings = Options { integers: Integers::String, ..Options::default() };
+    let as_strings = strings.scope(||memo(text));
+    assert_eq!(object(&as_strings, "forward").get("timeout"), Some(&Value::from("600000000000")));
+    assert_eq!(object(&as_strings, "json").get("n"), Some(&Value::from("18446744073709551615")));
+    let as_bigints = memo(text);
+    assert_eq!(object(&as_bigints, "forward").get("timeout"), Some(&Value::BigInt(600000000000)));
+    assert_eq!(object(&as_bigints, "json").get("n"), Some(&Value::BigInt(u64::MAX.into())));
+}
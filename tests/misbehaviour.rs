@@ -0,0 +1,49 @@
+//! The summary of the conflict in a Tendermint misbehaviour.
+
+// Only the client ID and the Tendermint headers are needed here.
+#[allow(dead_code)]
+mod common;
+
+use common::tendermint::{block_header, header};
+use namada_ibc_decoder::{Object, ToJS, Value};
+use namada_sdk::ibc::clients::tendermint::types::Misbehaviour;
+
+/// The conflict between headers at the given `(height, seconds, app_hash)`.
+fn conflict (header1: (u64, i64, u8), header2: (u64, i64, u8)) -> Object {
+    let misbehaviour = Misbehaviour::new(
+        common::client_id(),
+        header(block_header(header1.0, header1.1, header1.2)),
+        header(block_header(header2.0, header2.1, header2.2)),
+    );
+    let Value::Object(output) = misbehaviour.to_js().unwrap() else { panic!() };
+    let Some(Value::Object(conflict)) = output.get("conflict") else { panic!() };
+    conflict.clone()
+}
+
+fn kind (conflict: &Object) -> &Value {
+    conflict.get("kind").unwrap()
+}
+
+#[test]
+fn conflicting_block_hash () {
+    let conflict = conflict((100, 1_700_000_000, 0), (100, 1_700_000_000, 1));
+    assert_eq!(kind(&conflict), &Value::from("conflicting-block-hash"));
+    assert_ne!(conflict.get("hash1"), conflict.get("hash2"));
+}
+
+#[test]
+fn non_monotonic_time () {
+    let conflict = conflict((100, 1_700_000_000, 0), (101, 1_700_000_000, 0));
+    assert_eq!(kind(&conflict), &Value::from("non-monotonic-time"));
+    // Regardless of which header comes first.
+    let conflict = self::conflict((101, 1_699_999_999, 0), (100, 1_700_000_000, 0));
+    assert_eq!(kind(&conflict), &Value::from("non-monotonic-time"));
+}
+
+#[test]
+fn no_conflict () {
+    let conflict = conflict((100, 1_700_000_000, 0), (100, 1_700_000_000, 0));
+    assert_eq!(kind(&conflict), &Value::from("none"));
+    let conflict = self::conflict((100, 1_700_000_000, 0), (101, 1_700_000_001, 0));
+    assert_eq!(kind(&conflict), &Value::from("none"));
+}
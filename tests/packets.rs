@@ -0,0 +1,180 @@
+//! Packet data, acknowledgements and transfer tokens, interpreted by port.
+
+use sha2::{Sha256, Digest};
+use namada_ibc_decoder::{Object, PortAcknowledgement, PortPacketData, ToJS, Value};
+use namada_sdk::ibc::{
+    apps::transfer::types::PrefixedCoin,
+    core::{
+        channel::types::{
+            acknowledgement::Acknowledgement,
+            packet::Packet,
+            timeout::{TimeoutHeight, TimeoutTimestamp},
+        },
+        host::types::identifiers::{ChannelId, Sequence},
+    },
+    trace::ibc_token,
+};
+
+fn object (value: Value) -> Object {
+    let Value::Object(object) = value else { panic!("{value:?}") };
+    object
+}
+
+fn token (coin: &str) -> Object {
+    object(coin.parse::<PrefixedCoin>().unwrap().to_js().unwrap())
+}
+
+/// A packet from `channel-0` to `channel-1` of the given port.
+fn packet (port_id: &str, data: &[u8]) -> Packet {
+    Packet {
+        seq_on_a:               Sequence::from(1),
+        port_id_on_a:           port_id.parse().unwrap(),
+        chan_id_on_a:           ChannelId::new(0),
+        port_id_on_b:           port_id.parse().unwrap(),
+        chan_id_on_b:           ChannelId::new(1),
+        data:                   data.to_vec(),
+        timeout_height_on_b:    TimeoutHeight::Never,
+        timeout_timestamp_on_b: TimeoutTimestamp::Never,
+    }
+}
+
+fn packet_data (port_id: &str, data: &[u8]) -> Object {
+    object(PortPacketData { packet: &packet(port_id, data), received: false }.to_js().unwrap())
+}
+
+/// The token of an ICS-20 packet with the given denomination, received on `channel-1`.
+fn received_token (denom: &str) -> Object {
+    let data = format!(r#"{{"denom":"{denom}","amount":"1000","sender":"a","receiver":"b","memo":""}}"#);
+    let packet = packet("transfer", data.as_bytes());
+    let packet_data = object(PortPacketData { packet: &packet, received: true }.to_js().unwrap());
+    object(packet_data.get("token").unwrap().clone())
+}
+
+fn ibc_denom (denom: &str) -> Value {
+    Value::from(format!("ibc/{}", hex::encode_upper(Sha256::digest(denom.as_bytes()))))
+}
+
+fn acknowledgement (port_id: &str, data: &[u8]) -> Object {
+    let acknowledgement = Acknowledgement::try_from(data.to_vec()).unwrap();
+    object(PortAcknowledgement(&port_id.parse().unwrap(), &acknowledgement).to_js().unwrap())
+}
+
+#[test]
+fn ibc_denom_of_a_traced_token () {
+    // ATOM on Osmosis, as listed in the Cosmos chain registry.
+    let token = token("1000transfer/channel-0/uatom");
+    assert_eq!(token.get("denom"), Some(&Value::from("transfer/channel-0/uatom")));
+    assert_eq!(token.get("baseDenom"), Some(&Value::from("uatom")));
+    assert_eq!(token.get("ibcDenom"), Some(&Value::from(
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+    )));
+    assert_eq!(token.get("namadaToken"), Some(&Value::from(
+        ibc_token("transfer/channel-0/uatom").to_string()
+    )));
+    let Some(Value::Array(trace)) = token.get("trace") else { panic!() };
+    assert_eq!(trace.len(), 1);
+    let hop = object(trace[0].clone());
+    assert_eq!(hop.get("portId"), Some(&Value::from("transfer")));
+    assert_eq!(hop.get("channelId"), Some(&Value::from("channel-0")));
+}
+
+#[test]
+fn native_tokens_have_no_ibc_denom () {
+    // NAM on Namada mainnet.
+    let nam = "tnam1q9gr66cvu4hrzm0sd5kmlnjje82gs3xlfg3v6nu7";
+    let token = token(&format!("1000{nam}"));
+    assert_eq!(token.get("ibcDenom"), Some(&Value::Null));
+    assert_eq!(token.get("namadaToken"), Some(&Value::from(nam)));
+    let token = self::token("1000uosmo");
+    assert_eq!(token.get("ibcDenom"), Some(&Value::Null));
+    assert_eq!(token.get("namadaToken"), Some(&Value::Null));
+}
+
+#[test]
+fn received_foreign_tokens_are_prefixed_with_the_destination () {
+    for (denom, on_namada) in [
+        ("uatom", "transfer/channel-1/uatom"),
+        ("transfer/channel-7/uatom", "transfer/channel-1/transfer/channel-7/uatom"),
+    ] {
+        let token = received_token(denom);
+        // The token as sent, with its denomination on the receiving chain.
+        assert_eq!(token.get("denom"), Some(&Value::from(denom)));
+        assert_eq!(token.get("ibcDenom"), Some(&ibc_denom(on_namada)), "{denom}");
+        assert_eq!(token.get("namadaToken"), Some(&Value::from(ibc_token(on_namada).to_string())), "{denom}");
+    }
+}
+
+#[test]
+fn received_returning_tokens_lose_the_source_prefix () {
+    let token = received_token("transfer/channel-0/transfer/channel-5/uatom");
+    assert_eq!(token.get("ibcDenom"), Some(&ibc_denom("transfer/channel-5/uatom")));
+    assert_eq!(token.get("namadaToken"), Some(&Value::from(ibc_token("transfer/channel-5/uatom").to_string())));
+    // NAM, returning to Namada.
+    let nam = "tnam1q9gr66cvu4hrzm0sd5kmlnjje82gs3xlfg3v6nu7";
+    let token = received_token(&format!("transfer/channel-0/{nam}"));
+    assert_eq!(token.get("ibcDenom"), Some(&Value::Null));
+    assert_eq!(token.get("namadaToken"), Some(&Value::from(nam)));
+}
+
+#[test]
+fn ics20_packet_data () {
+    let data = br#"{"denom":"transfer/channel-0/uosmo","amount":"1000","sender":"a","receiver":"b","memo":""}"#;
+    let packet_data = packet_data("transfer", data);
+    assert_eq!(packet_data.get("kind"), Some(&Value::from("ics20")));
+    assert_eq!(packet_data.get("denom"), Some(&Value::from("transfer/channel-0/uosmo")));
+    assert_eq!(packet_data.get("amount"), Some(&Value::from("1000")));
+}
+
+#[test]
+fn ics721_packet_data () {
+    let data = br#"{"classId":"nft-transfer/channel-2/class","tokenIds":["token-1"],"sender":"a","receiver":"b"}"#;
+    let packet_data = packet_data("nft-transfer", data);
+    assert_eq!(packet_data.get("kind"), Some(&Value::from("ics721")));
+    assert_eq!(packet_data.get("sender"), Some(&Value::from("a")));
+}
+
+#[test]
+fn other_packet_data_as_text_or_bytes () {
+    let text = packet_data("icahost", b"some text");
+    assert_eq!(text.get("kind"), Some(&Value::from("text")));
+    assert_eq!(text.get("text"), Some(&Value::from("some text")));
+    let bytes = packet_data("icahost", &[0xff, 0xfe]);
+    assert_eq!(bytes.get("kind"), Some(&Value::from("bytes")));
+    assert_eq!(bytes.get("bytes"), Some(&Value::from(&[0xff, 0xfe][..])));
+}
+
+#[test]
+fn invalid_ics20_packet_data () {
+    let packet = packet("transfer", b"not json");
+    let error = PortPacketData { packet: &packet, received: false }.to_js().unwrap_err();
+    assert_eq!(error.stage, namada_ibc_decoder::Stage::PacketData);
+}
+
+#[test]
+fn successful_acknowledgement () {
+    let ack = acknowledgement("transfer", br#"{"result":"AQ=="}"#);
+    assert_eq!(ack.get("status"), Some(&Value::from("success")));
+    assert_eq!(ack.get("result"), Some(&Value::from(&[1u8][..])));
+    assert_eq!(ack.get("error"), Some(&Value::Null));
+    let app = object(ack.get("app").unwrap().clone());
+    assert_eq!(app.get("kind"), Some(&Value::from("ics20")));
+    assert_eq!(app.get("success"), Some(&Value::from(true)));
+}
+
+#[test]
+fn error_acknowledgement () {
+    let ack = acknowledgement("transfer", br#"{"error":"insufficient funds"}"#);
+    assert_eq!(ack.get("status"), Some(&Value::from("error")));
+    assert_eq!(ack.get("result"), Some(&Value::Null));
+    assert_eq!(ack.get("error"), Some(&Value::from("insufficient funds")));
+    let app = object(ack.get("app").unwrap().clone());
+    assert_eq!(app.get("success"), Some(&Value::from(false)));
+}
+
+#[test]
+fn unknown_acknowledgement () {
+    let ack = acknowledgement("icahost", b"\x01");
+    assert_eq!(ack.get("status"), Some(&Value::from("unknown")));
+    assert_eq!(ack.get("app"), Some(&Value::Null));
+    assert_eq!(ack.get("raw"), Some(&Value::from(&b"\x01"[..])));
+}
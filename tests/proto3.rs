@@ -0,0 +1,161 @@
+//! Proto3 JSON output, compared with the serde JSON of the ibc-proto types,
+//! which have the same field names as the protobuf definitions.
+//!
+//! The serde JSON of the Tendermint types differs from proto3 JSON in more than
+//! the representation of values, so only the fields of those are compared.
+
+mod common;
+
+use base64::prelude::*;
+use prost::Message;
+use serde_json::Value as Json;
+use namada_ibc_decoder::{encode, proto3, schema};
+use namada_sdk::ibc::primitives::proto::Any;
+use ibc_proto::ibc::{
+    core::{client::v1 as client, connection::v1 as connection, channel::v1 as channel},
+    applications::{transfer::v1 as transfer, nft_transfer::v1 as nft_transfer},
+    lightclients::tendermint::v1 as tendermint,
+};
+
+/// Decode the encoded message with the ibc-proto type for its type URL,
+/// and serialize that.
+fn ibc_proto_json (any: &Any) -> Json {
+    macro_rules! to_json {
+        ($($raw:ty),* $(,)?) => {
+            $(if any.type_url == <$raw as prost::Name>::type_url() {
+                return serde_json::to_value(<$raw>::decode(any.value.as_slice()).unwrap()).unwrap()
+            })*
+        }
+    }
+    to_json!(
+        client::MsgCreateClient, client::MsgUpdateClient, client::MsgUpgradeClient,
+        client::MsgSubmitMisbehaviour, client::MsgRecoverClient,
+        connection::MsgConnectionOpenInit, connection::MsgConnectionOpenTry,
+        connection::MsgConnectionOpenAck, connection::MsgConnectionOpenConfirm,
+        channel::MsgChannelOpenInit, channel::MsgChannelOpenTry,
+        channel::MsgChannelOpenAck, channel::MsgChannelOpenConfirm,
+        channel::MsgChannelCloseInit, channel::MsgChannelCloseConfirm,
+        channel::MsgRecvPacket, channel::MsgAcknowledgement,
+        channel::MsgTimeout, channel::MsgTimeoutOnClose,
+        transfer::MsgTransfer, nft_transfer::MsgTransfer,
+    );
+    panic!("no ibc-proto type for {}", any.type_url)
+}
+
+/// Bring both outputs to the same representation of values: ibc-proto's serde JSON
+/// has integers as numbers, bytes as arrays of numbers, enums as numbers, and
+/// `Any`s as `{ type_url, value }`.
+fn normalize (json: &Json) -> Json {
+    match json {
+        Json::Object(fields) => Json::Object(fields.iter().map(|(key, value)|match key.as_str() {
+            "@type" => ("type_url".to_string(), normalize(value)),
+            // Enums are output by name, and compared by key only.
+            "state" | "ordering" => (key.clone(), Json::Null),
+            // ibc-rs encodes a timeout height of "never" as a missing height,
+            // which ibc-go, where the field isn't nullable, outputs as zeros.
+            "timeout_height" if value.is_null() => (key.clone(), serde_json::json!({
+                "revision_number": "0",
+                "revision_height": "0",
+            })),
+            _ => (key.clone(), normalize(value)),
+        }).collect()),
+        // None of the repeated fields in the samples is empty, so an empty array is bytes.
+        Json::Array(items) if items.iter().all(|item|item.as_u64().is_some_and(|n|n < 256)) => {
+            let bytes: Vec<u8> = items.iter().map(|item|item.as_u64().unwrap() as u8).collect();
+            Json::from(BASE64_STANDARD.encode(bytes))
+        },
+        Json::Array(items) => Json::Array(items.iter().map(normalize).collect()),
+        Json::Number(number) => Json::from(number.to_string()),
+        _ => json.clone(),
+    }
+}
+
+#[test]
+fn same_fields_as_ibc_proto () {
+    for sample in common::samples() {
+        let Json::Object(mut output) = proto3::to_json(&sample).unwrap() else { panic!() };
+        assert_eq!(output.remove("schemaVersion"), Some(Json::from(schema::VERSION)));
+        let type_url = output.remove("@type").unwrap();
+        let any = Any::decode(encode::encode(&sample).unwrap().as_slice()).unwrap();
+        assert_eq!(type_url, Json::from(any.type_url.as_str()));
+        assert_eq!(
+            normalize(&Json::Object(output)),
+            normalize(&ibc_proto_json(&any)),
+            "{}", any.type_url
+        );
+    }
+}
+
+#[test]
+fn schema_version_follows_type () {
+    for sample in common::samples() {
+        let Json::Object(output) = proto3::to_json(&sample).unwrap() else { panic!() };
+        let keys: Vec<_> = output.keys().take(2).collect();
+        assert_eq!(keys, ["@type", "schemaVersion"]);
+    }
+}
+
+/// The keys of the serde JSON of a nested Tendermint `Any`, decoded with its ibc-proto type.
+fn tendermint_keys (any: &Json) -> Vec<String> {
+    let type_url = any["type_url"].as_str().unwrap();
+    let bytes: Vec<u8> = serde_json::from_value(any["value"].clone()).unwrap();
+    macro_rules! to_json {
+        ($($raw:ty),* $(,)?) => {
+            $(if type_url == <$raw as prost::Name>::type_url() {
+                let raw = <$raw>::decode(bytes.as_slice()).unwrap();
+                let Json::Object(fields) = serde_json::to_value(raw).unwrap() else { panic!() };
+                return fields.keys().cloned().collect()
+            })*
+        }
+    }
+    to_json!(tendermint::ClientState, tendermint::ConsensusState, tendermint::Header, tendermint::Misbehaviour);
+    panic!("no ibc-proto type for {type_url}")
+}
+
+#[test]
+fn tendermint_anys_are_expanded () {
+    for sample in common::tendermint::samples() {
+        let output = proto3::to_json(&sample).unwrap();
+        let any = Any::decode(encode::encode(&sample).unwrap().as_slice()).unwrap();
+        let Json::Object(expected) = ibc_proto_json(&any) else { panic!() };
+        for (key, value) in expected.iter().filter(|(_, value)|value.get("type_url").is_some()) {
+            let Json::Object(nested) = &output[key] else { panic!("{key} in {}", any.type_url) };
+            let mut keys = vec!["@type".to_string()];
+            keys.extend(tendermint_keys(value));
+            assert_eq!(nested.keys().cloned().collect::<Vec<_>>(), keys, "{key} in {}", any.type_url);
+            assert_eq!(nested["@type"], value["type_url"]);
+        }
+    }
+}
+
+#[test]
+fn tendermint_values () {
+    let samples = common::tendermint::samples();
+    let output = |type_url: &str|samples.iter()
+        .map(|sample|proto3::to_json(sample).unwrap())
+        .find(|output|output["@type"] == type_url)
+        .unwrap();
+
+    let create = output("/ibc.core.client.v1.MsgCreateClient");
+    let client_state = &create["client_state"];
+    assert_eq!(client_state["chain_id"], "namada-test");
+    assert_eq!(client_state["trust_level"], serde_json::json!({ "numerator": "1", "denominator": "3" }));
+    assert_eq!(client_state["trusting_period"], "1209600s");
+    assert_eq!(client_state["max_clock_drift"], "20s");
+    assert_eq!(client_state["latest_height"]["revision_height"], "100");
+    assert_eq!(client_state["proof_specs"][0]["leaf_spec"]["hash"], "SHA256");
+    let consensus_state = &create["consensus_state"];
+    assert_eq!(consensus_state["root"]["hash"], BASE64_STANDARD.encode([10; 32]));
+    assert_eq!(consensus_state["timestamp"], "2023-11-14T22:13:20Z");
+
+    let update = output("/ibc.core.client.v1.MsgUpdateClient");
+    let header = &update["client_message"];
+    assert_eq!(header["signed_header"]["header"]["height"], "100");
+    assert_eq!(header["signed_header"]["header"]["app_hash"], BASE64_STANDARD.encode([0; 32]));
+    assert_eq!(header["signed_header"]["commit"]["height"], "100");
+    assert_eq!(header["trusted_height"]["revision_height"], "1");
+
+    let misbehaviour = output("/ibc.core.client.v1.MsgSubmitMisbehaviour");
+    let misbehaviour = &misbehaviour["misbehaviour"];
+    assert_eq!(misbehaviour["header_2"]["signed_header"]["header"]["app_hash"], BASE64_STANDARD.encode([1; 32]));
+}
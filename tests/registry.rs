@@ -0,0 +1,98 @@
+//! Decoders of `Any` values registered at runtime, and the built-in ones they replace.
+//! The registry is per thread, so each test starts from the built-in decoders.
+
+mod common;
+
+use std::rc::Rc;
+use namada_ibc_decoder::{decode_ibc, encode, registry, DecodeError, Object, Options, Stage, Value};
+use namada_sdk::ibc::{
+    clients::tendermint::types::{
+        TENDERMINT_HEADER_TYPE_URL, TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+        TENDERMINT_CLIENT_STATE_TYPE_URL, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+    },
+    primitives::proto::Any,
+};
+
+fn object (value: &Value) -> &Object {
+    let Value::Object(object) = value else { panic!("{value:?}") };
+    object
+}
+
+/// The `clientState` of the client creation sample, whose `Any` is a `/test.v1.Sample`.
+fn client_state () -> Object {
+    let bytes = encode::encode(&common::samples().remove(0)).unwrap();
+    object(object(&decode_ibc(&bytes).unwrap()).get("clientState").unwrap()).clone()
+}
+
+/// The `clientMessage` of the client update sample, whose `Any` is a Tendermint header.
+fn client_message () -> Object {
+    let bytes = encode::encode(&common::tendermint::samples().remove(1)).unwrap();
+    object(object(&decode_ibc(&bytes).unwrap()).get("clientMessage").unwrap()).clone()
+}
+
+#[test]
+fn registered_decoder () {
+    registry::register("/test.v1.Sample", Rc::new(|any: &Any|Ok(Value::from(any.value.len() as u32))));
+    let client_state = client_state();
+    assert_eq!(client_state.get("value"), Some(&Value::from(3u32)));
+    // The bytes are kept for re-encoding.
+    assert_eq!(client_state.get("raw"), Some(&Value::from(&[4u8, 5, 6][..])));
+}
+
+#[test]
+fn registered_decoder_replaces_a_built_in_one () {
+    assert!(object(client_message().get("value").unwrap()).get("signedHeader").is_some());
+    registry::register(TENDERMINT_HEADER_TYPE_URL, Rc::new(|_: &Any|Ok(Value::from("header"))));
+    assert_eq!(client_message().get("value"), Some(&Value::from("header")));
+}
+
+#[test]
+fn unregistered_type_urls_are_opaque () {
+    // A registered decoder.
+    registry::register("/test.v1.Sample", Rc::new(|_: &Any|Ok(Value::Null)));
+    assert!(registry::unregister("/test.v1.Sample"));
+    assert!(!registry::unregister("/test.v1.Sample"));
+    let client_state = client_state();
+    assert_eq!(client_state.get("value"), Some(&Value::from(&[4u8, 5, 6][..])));
+    assert_eq!(client_state.get("raw"), None);
+    // A built-in decoder.
+    assert!(registry::unregister(TENDERMINT_HEADER_TYPE_URL));
+    let client_message = client_message();
+    assert!(matches!(client_message.get("value"), Some(Value::Bytes(_))));
+    assert_eq!(client_message.get("raw"), None);
+}
+
+#[test]
+fn type_urls () {
+    let mut built_in = vec![
+        TENDERMINT_CLIENT_STATE_TYPE_URL, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+        TENDERMINT_HEADER_TYPE_URL, TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+    ];
+    built_in.sort();
+    assert_eq!(registry::type_urls(), built_in);
+    registry::register("/test.v1.Sample", Rc::new(|_: &Any|Ok(Value::Null)));
+    registry::unregister(TENDERMINT_MISBEHAVIOUR_TYPE_URL);
+    let mut expected = vec!["/test.v1.Sample", TENDERMINT_CLIENT_STATE_TYPE_URL,
+        TENDERMINT_CONSENSUS_STATE_TYPE_URL, TENDERMINT_HEADER_TYPE_URL];
+    expected.sort();
+    assert_eq!(registry::type_urls(), expected);
+}
+
+#[test]
+fn failing_decoder () {
+    registry::register("/test.v1.Sample", Rc::new(|_: &Any|Err(DecodeError::conversion(Stage::Any, "failed"))));
+    let bytes = encode::encode(&common::samples().remove(0)).unwrap();
+    let error = decode_ibc(&bytes).unwrap_err();
+    assert_eq!(error.stage, Stage::Any);
+    assert_eq!(error.type_url.as_deref(), Some("/test.v1.Sample"));
+    assert_eq!(error.path, ["clientState"]);
+    assert!(error.message.contains("failed"));
+    // In lenient mode, the failure is in place of the value.
+    let lenient = Options { lenient: true, ..Options::default() };
+    let output = lenient.scope(||decode_ibc(&bytes)).unwrap();
+    let client_state = object(object(&output).get("clientState").unwrap());
+    let failure = object(client_state.get("value").unwrap());
+    assert_eq!(failure.get("raw"), Some(&Value::from(&[4u8, 5, 6][..])));
+    let Some(Value::Array(warnings)) = object(&output).get("warnings") else { panic!() };
+    assert_eq!(warnings.len(), 1);
+}
@@ -0,0 +1,84 @@
+//! Round trips between `encode` and `decode`, for every message variant.
+
+mod common;
+
+use namada_ibc_decoder::{decode, encode, DecodedIbc, ToJS, Value};
+
+/// The samples, plus the ones with Tendermint light client values, which are
+/// decoded and have to be re-encoded from their bytes, plus transfers with
+/// a Namada transfer attached, which are borsh-encoded instead of protobuf-encoded:
+/// one with accounts and a shielded section, and one without.
+fn samples () -> Vec<DecodedIbc> {
+    let mut samples = common::samples();
+    samples.extend(common::tendermint::samples());
+    samples.extend(common::namada::samples());
+    for sample in common::samples() {
+        if let DecodedIbc::Transfer(mut message) = sample {
+            message.transfer = Some(namada_sdk::token::Transfer {
+                sources:               Default::default(),
+                targets:               Default::default(),
+                shielded_section_hash: None,
+            });
+            samples.push(DecodedIbc::Transfer(message));
+        }
+    }
+    samples
+}
+
+#[test]
+fn encoded_messages_decode_to_the_same_output () {
+    for sample in samples() {
+        let expected = sample.to_js().unwrap();
+        let bytes = encode::encode(&sample).unwrap();
+        let decoded = decode(&bytes).unwrap_or_else(|e|panic!("{e}: {expected:?}"));
+        assert_eq!(decoded.to_js().unwrap(), expected);
+    }
+}
+
+#[test]
+fn output_reads_back_to_the_same_message () {
+    for sample in samples() {
+        let output = sample.to_js().unwrap();
+        let message = encode::from_value(&output).unwrap_or_else(|e|panic!("{e}: {output:?}"));
+        assert_eq!(message.to_js().unwrap(), output);
+    }
+}
+
+#[test]
+fn serialized_output_reads_back_to_the_same_message () {
+    for sample in samples() {
+        let output = sample.to_js().unwrap();
+        let json = Value::from(&output.to_json());
+        let message = encode::from_value(&json).unwrap_or_else(|e|panic!("{e}: {json:?}"));
+        assert_eq!(message.to_js().unwrap(), output);
+    }
+}
+
+#[test]
+fn output_re_encodes_to_the_same_bytes () {
+    for sample in samples() {
+        let output = sample.to_js().unwrap();
+        let message = encode::from_value(&Value::from(&output.to_json())).unwrap();
+        assert_eq!(encode::encode(&message).unwrap(), encode::encode(&sample).unwrap(), "{output:?}");
+    }
+}
+
+#[test]
+fn proofs_are_output_as_their_raw_bytes () {
+    assert_eq!(common::proof().to_js().unwrap(), Value::from(&[1u8, 2, 3][..]));
+}
+
+#[test]
+fn decoded_anys_keep_their_bytes () {
+    let any = common::tendermint::client_state_any();
+    let Value::Object(output) = any.to_js().unwrap() else { panic!() };
+    assert!(matches!(output.get("value"), Some(Value::Object(_))));
+    assert_eq!(output.get("raw"), Some(&Value::from(&any.value[..])));
+}
+
+#[test]
+fn unknown_message_type () {
+    let mut object = namada_ibc_decoder::Object::new();
+    object.set("type", Value::from("envelope.client.frobnicate"));
+    assert!(encode::from_value(&Value::from(object)).is_err());
+}
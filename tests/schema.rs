@@ -0,0 +1,142 @@
+//! Golden tests that lock the shape of the decoder's output to the schema.
+
+mod common;
+
+use namada_ibc_decoder::{ToJS, Value, Object, schema};
+
+/// The full output for each sample, in the order of [`golden_samples`].
+const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/messages.json");
+
+fn object (value: &Value) -> &Object {
+    match value {
+        Value::Object(object) => object,
+        other => panic!("expected an object, got {other:?}"),
+    }
+}
+
+fn keys (value: &Value) -> Vec<&str> {
+    object(value).iter().map(|(key, _)|key).collect()
+}
+
+fn field<'a> (value: &'a Value, key: &str) -> &'a Value {
+    object(value).get(key).unwrap_or_else(||panic!("missing {key}"))
+}
+
+fn decoded () -> Vec<Value> {
+    common::samples().iter().map(|sample|sample.to_js().unwrap()).collect()
+}
+
+/// One sample of every message variant, then the samples with Tendermint light client
+/// values and with Namada transfers, whose shapes the opaque samples don't cover.
+fn golden_samples () -> Vec<Value> {
+    let mut samples = common::samples();
+    samples.extend(common::tendermint::samples());
+    samples.extend(common::namada::samples());
+    samples.iter().map(|sample|sample.to_js().unwrap()).collect()
+}
+
+fn golden () -> Vec<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(GOLDEN).unwrap()).unwrap()
+}
+
+/// The golden output must have been recorded with the current version.
+#[test]
+fn schema_version () {
+    for message in golden() {
+        assert_eq!(message["schemaVersion"], schema::VERSION, "{GOLDEN} is not of the current schema version");
+    }
+    for message in golden_samples() {
+        assert_eq!(field(&message, "schemaVersion"), &Value::from(schema::VERSION));
+    }
+}
+
+/// Serialized as text, so that the order of keys counts too.
+fn serialize (output: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(output).unwrap() + "\n"
+}
+
+#[test]
+fn every_variant_matches_its_golden_output () {
+    let outputs = serde_json::Value::from(golden_samples().iter().map(Value::to_json).collect::<Vec<_>>());
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(GOLDEN, serialize(&outputs)).unwrap();
+    }
+    let (outputs, golden) = (outputs.as_array().unwrap(), golden());
+    assert_eq!(outputs.len(), golden.len(), "{GOLDEN} has a different number of samples");
+    for (output, golden) in outputs.iter().zip(&golden) {
+        assert!(
+            serialize(output) == serialize(golden),
+            "output of {} differs from {GOLDEN}:\n{}\
+            If the change is intended, bump schema::VERSION and run `just golden` to update it.",
+            output["type"], serialize(output),
+        );
+    }
+}
+
+#[test]
+fn every_variant_matches_its_schema () {
+    for message in decoded() {
+        let Value::String(name) = field(&message, "type") else {
+            panic!("type is not a string: {message:?}")
+        };
+        let schema = schema::Message::get(name).unwrap_or_else(||panic!("no schema for {name}"));
+        assert_eq!(keys(&message), schema.keys(), "keys of {name}");
+    }
+}
+
+#[test]
+fn every_schema_has_a_variant () {
+    let decoded = decoded();
+    let names: Vec<_> = decoded.iter().map(|message|field(message, "type")).collect();
+    for message in schema::MESSAGES {
+        assert!(names.contains(&&Value::from(message.name)), "no sample of {}", message.name);
+    }
+    assert_eq!(names.len(), schema::MESSAGES.len());
+}
+
+#[test]
+fn shared_shapes () {
+    let decoded = decoded();
+    let get = |name: &str| decoded.iter()
+        .find(|message|field(message, "type") == &Value::from(name))
+        .unwrap();
+
+    let recv = get("envelope.packet.recv");
+    assert_eq!(keys(field(recv, "proofHeightOnA")), ["revisionNumber", "revisionHeight"]);
+    let packet = field(recv, "packet");
+    assert_eq!(keys(packet), [
+        "seqOnA", "portIdOnA", "chanIdOnA", "portIdOnB", "chanIdOnB",
+        "data", "packetData", "timeoutHeightOnB", "timeoutTimestampOnB",
+    ]);
+    assert_eq!(field(packet, "timeoutTimestampOnB"), &Value::from("never"));
+    assert_eq!(keys(field(packet, "packetData")), [
+        "kind", "denom", "amount", "token", "sender", "receiver", "memo",
+    ]);
+
+    let ack = field(get("envelope.packet.ack"), "acknowledgement");
+    assert_eq!(keys(ack), ["status", "result", "error", "app", "raw"]);
+    assert_eq!(field(ack, "status"), &Value::from("success"));
+
+    let open_init = get("envelope.connection.open_init");
+    assert_eq!(keys(field(open_init, "counterparty")), ["clientId", "connectionId", "prefix"]);
+    assert_eq!(keys(field(open_init, "version")), ["identifier", "features"]);
+    let delay_period = field(open_init, "delayPeriod");
+    assert_eq!(keys(delay_period), ["nanos", "iso"]);
+    assert_eq!(field(delay_period, "iso"), &Value::from("PT86400S"));
+    let open_try = get("envelope.connection.open_try");
+    assert_eq!(field(field(open_try, "delayPeriod"), "iso"), &Value::from("PT1.5S"));
+
+    let open_ack = get("envelope.channel.open_ack");
+    assert_eq!(field(open_ack, "chanIdOnA"), &Value::from("channel-0"));
+    assert_eq!(field(open_ack, "chanIdOnB"), &Value::from("channel-1"));
+
+    let transfer = field(get("transfer"), "message");
+    assert_eq!(keys(transfer), [
+        "portIdOnA", "chanIdOnA", "packetData", "timeoutHeightOnB", "timeoutTimestampOnB",
+    ]);
+    let token = field(field(transfer, "packetData"), "token");
+    assert_eq!(keys(token), [
+        "denom", "amount", "baseDenom", "trace", "ibcDenom", "namadaToken",
+    ]);
+    assert_eq!(field(token, "baseDenom"), &Value::from("uosmo"));
+}
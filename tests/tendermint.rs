@@ -0,0 +1,121 @@
+//! The Tendermint light client values in decoded messages.
+
+mod common;
+
+use namada_ibc_decoder::{decode_ibc, encode, options::ValidatorSets, DecodedIbc, Options, ToJS, Value};
+use namada_sdk::ibc::core::client::context::types::msgs::ClientMsg;
+
+/// The value at the given dot-separated path.
+fn get<'a> (value: &'a Value, path: &str) -> &'a Value {
+    path.split('.').fold(value, |value, key|match value {
+        Value::Object(object) => object.get(key).unwrap_or_else(||panic!("no {key} in {path}")),
+        _ => panic!("no {key} in {path}"),
+    })
+}
+
+/// The first Tendermint sample that matches, decoded with the given options.
+fn decoded (options: Options, matches: impl Fn(&DecodedIbc) -> bool) -> Value {
+    let sample = common::tendermint::samples().into_iter().find(matches).unwrap();
+    let bytes = encode::encode(&sample).unwrap();
+    options.scope(||decode_ibc(&bytes)).unwrap()
+}
+
+/// The Tendermint header of the client update sample, decoded with the given options.
+fn header (options: Options) -> Value {
+    let update = decoded(options, |sample|matches!(sample, DecodedIbc::Client(ClientMsg::UpdateClient(_))));
+    get(&update, "clientMessage.value").clone()
+}
+
+fn bytes (byte: u8, len: usize) -> Value {
+    Value::from(vec![byte; len].as_slice())
+}
+
+#[test]
+fn header_fields () {
+    let header = header(Options::default());
+    let block = get(&header, "signedHeader.header");
+    assert_eq!(get(block, "chainId"), &Value::from("namada-test"));
+    assert_eq!(get(block, "height"), &Value::BigInt(100));
+    assert_eq!(get(block, "time"), &Value::from("2023-11-14T22:13:20Z"));
+    assert_eq!(get(block, "lastBlockId.hash"), &bytes(1, 32));
+    assert_eq!(get(block, "lastBlockId.partSetHeader.total"), &Value::from(1u32));
+    assert_eq!(get(block, "validatorsHash"), &bytes(5, 32));
+    assert_eq!(get(block, "appHash"), &bytes(0, 32));
+    assert_eq!(get(block, "proposerAddress"), &bytes(9, 20));
+    let block_hash = common::tendermint::block_header(100, 1_700_000_000, 0).hash().to_js().unwrap();
+    let commit = get(&header, "signedHeader.commit");
+    assert_eq!(get(commit, "height"), &Value::BigInt(100));
+    assert_eq!(get(commit, "round"), &Value::from(0u32));
+    assert_eq!(get(commit, "blockId.hash"), &block_hash);
+    assert_eq!(get(commit, "signatures"), &Value::Array(vec![]));
+    assert_eq!(get(&header, "trustedHeight.revisionNumber"), &Value::BigInt(0));
+    assert_eq!(get(&header, "trustedHeight.revisionHeight"), &Value::BigInt(1));
+    let expected_hash = common::tendermint::validators().hash().to_js().unwrap();
+    assert_eq!(get(&header, "validatorSet.hash"), &expected_hash);
+    assert_eq!(get(&header, "trustedNextValidatorSet.hash"), &expected_hash);
+}
+
+#[test]
+fn client_and_consensus_state_fields () {
+    let create = decoded(Options::default(), |sample|matches!(sample, DecodedIbc::Client(ClientMsg::CreateClient(_))));
+    let client_state = get(&create, "clientState.value");
+    assert_eq!(get(client_state, "chainId"), &Value::from("namada-test"));
+    assert_eq!(get(client_state, "trustLevel.numerator"), &Value::BigInt(1));
+    assert_eq!(get(client_state, "trustLevel.denominator"), &Value::BigInt(3));
+    for (key, seconds) in [
+        ("trustingPeriod", 14 * 86400),
+        ("unbondingPeriod", 21 * 86400),
+        ("maxClockDrift", 20),
+    ] {
+        assert_eq!(get(client_state, &format!("{key}.nanos")), &Value::BigInt(seconds * 1_000_000_000), "{key}");
+        assert_eq!(get(client_state, &format!("{key}.iso")), &Value::from(format!("PT{seconds}S")), "{key}");
+    }
+    assert_eq!(get(client_state, "latestHeight.revisionNumber"), &Value::BigInt(0));
+    assert_eq!(get(client_state, "latestHeight.revisionHeight"), &Value::BigInt(100));
+    assert_eq!(get(client_state, "frozenHeight"), &Value::Null);
+    let Value::Array(proof_specs) = get(client_state, "proofSpecs") else { panic!() };
+    // The IAVL and Tendermint specs of the Cosmos SDK.
+    assert_eq!(proof_specs.len(), 2);
+    for (spec, child_size) in proof_specs.iter().zip([33, 32]) {
+        assert_eq!(get(spec, "leafSpec.hash"), &Value::from("SHA256"));
+        assert_eq!(get(spec, "leafSpec.prehashValue"), &Value::from("SHA256"));
+        assert_eq!(get(spec, "leafSpec.length"), &Value::from("VAR_PROTO"));
+        assert_eq!(get(spec, "innerSpec.childOrder"), &Value::Array(vec![Value::from(0), Value::from(1)]));
+        assert_eq!(get(spec, "innerSpec.childSize"), &Value::from(child_size));
+    }
+    assert_eq!(get(client_state, "upgradePath"), &Value::Array(vec![
+        Value::from("upgrade"), Value::from("upgradedIBCState"),
+    ]));
+    let consensus_state = get(&create, "consensusState.value");
+    assert_eq!(get(consensus_state, "timestamp"), &Value::from("2023-11-14T22:13:20Z"));
+    assert_eq!(get(consensus_state, "root"), &bytes(10, 32));
+    assert_eq!(get(consensus_state, "nextValidatorsHash"), &bytes(5, 32));
+}
+
+#[test]
+fn full_validator_sets () {
+    let header = header(Options::default());
+    let expected_hash = common::tendermint::validators().hash().to_js().unwrap();
+    for key in ["validatorSet", "trustedNextValidatorSet"] {
+        let set = get(&header, key);
+        assert_eq!(get(set, "hash"), &expected_hash, "{key}");
+        let Value::Array(validators) = get(set, "validators") else { panic!("{key}") };
+        assert_eq!(validators.len(), 2, "{key}");
+        // Sorted by voting power; the proposer is the second one.
+        assert_eq!(get(&validators[0], "power"), &Value::BigInt(20), "{key}");
+        assert_eq!(get(&validators[1], "power"), &Value::BigInt(10), "{key}");
+        assert_eq!(get(set, "proposer"), &validators[1], "{key}");
+        assert_eq!(get(set, "totalVotingPower"), &Value::BigInt(30), "{key}");
+    }
+}
+
+#[test]
+fn validator_set_hashes () {
+    let header = header(Options { validator_sets: ValidatorSets::Hash, ..Options::default() });
+    let expected_hash = common::tendermint::validators().hash().to_js().unwrap();
+    for key in ["validatorSet", "trustedNextValidatorSet"] {
+        let Value::Object(set) = get(&header, key) else { panic!("{key}") };
+        assert_eq!(set.iter().map(|(key, _)|key).collect::<Vec<_>>(), ["hash"], "{key}");
+        assert_eq!(set.get("hash"), Some(&expected_hash), "{key}");
+    }
+}
@@ -0,0 +1,54 @@
+//! Namada transfers attached to IBC transfers.
+
+// Only the Namada transfer samples are needed here.
+#[allow(dead_code)]
+mod common;
+
+use common::namada::{amount, NAM};
+use namada_ibc_decoder::{Object, ToJS, Value};
+
+fn object (value: &Value) -> &Object {
+    let Value::Object(object) = value else { panic!("{value:?}") };
+    object
+}
+
+fn array (value: &Value) -> &[Value] {
+    let Value::Array(array) = value else { panic!("{value:?}") };
+    array
+}
+
+fn denominated (units: &str, denom: u32) -> Value {
+    let mut object = Object::new();
+    object.set("amount", Value::from(units));
+    object.set("denom", Value::from(denom));
+    Value::from(object)
+}
+
+#[test]
+fn amounts_are_in_base_units_with_their_denomination () {
+    // The display form of this one is "1", which loses both.
+    assert_eq!(amount(1_000_000, 6).to_js().unwrap(), denominated("1000000", 6));
+    assert_eq!(amount(1_500, 3).to_js().unwrap(), denominated("1500", 3));
+    assert_eq!(amount(42, 0).to_js().unwrap(), denominated("42", 0));
+}
+
+#[test]
+fn sources_and_targets () {
+    for sample in common::namada::samples() {
+        let output = sample.to_js().unwrap();
+        let transfer = object(object(&output).get("transfer").unwrap());
+        for key in ["sources", "targets"] {
+            let accounts = array(transfer.get(key).unwrap());
+            assert_eq!(accounts.len(), 1, "{key}");
+            let account = object(&accounts[0]);
+            assert_eq!(account.get("token"), Some(&Value::from(NAM)));
+            assert_eq!(account.get("amount"), Some(&denominated("1000000", 6)));
+        }
+        let source = object(&array(transfer.get("sources").unwrap())[0]);
+        assert_eq!(source.get("owner"), Some(&Value::from(common::signer().as_ref())));
+        assert_eq!(
+            transfer.get("shieldedSectionHash"),
+            Some(&Value::from(namada_sdk::hash::Hash([7; 32]).to_string()))
+        );
+    }
+}
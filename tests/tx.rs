@@ -0,0 +1,84 @@
+//! Decoding of whole Namada transactions, with several inner transactions.
+
+mod common;
+
+use namada_ibc_decoder::{decode_tx, encode, tx_hash, ErrorKind, Object, Stage, ToJS, Value};
+use namada_sdk::{
+    borsh::BorshSerializeExt,
+    hash::Hash,
+    tx::{data::TxType, Code, Data, Section, Tx, TxCommitments},
+};
+
+/// A transaction with one inner transaction per `(tag, data)`,
+/// in that order in the batch.
+fn tx (inner: &[(&str, Vec<u8>)]) -> Tx {
+    let mut tx = Tx::from_type(TxType::Raw);
+    tx.header.batch.clear();
+    for (tag, data) in inner {
+        let code_hash = tx.add_section(Section::Code(Code::new(vec![], Some(tag.to_string())))).get_hash();
+        let data_hash = tx.add_section(Section::Data(Data::new(data.clone()))).get_hash();
+        tx.header.batch.insert(TxCommitments { code_hash, data_hash, memo_hash: Hash::default() });
+    }
+    tx
+}
+
+fn entries (tx: &Tx) -> Vec<Object> {
+    decode_tx(&tx.serialize_to_vec()).unwrap().into_iter().map(|entry|match entry {
+        Value::Object(entry) => entry,
+        entry => panic!("{entry:?}"),
+    }).collect()
+}
+
+#[test]
+fn decodes_ibc_inner_txs_and_reports_failures () {
+    let sample = common::samples().remove(0);
+    let valid = encode::encode(&sample).unwrap();
+    let tx = tx(&[
+        ("tx_ibc.wasm",      valid),
+        ("tx_transfer.wasm", b"not ibc".to_vec()),
+        ("tx_ibc.wasm",      b"not ibc either".to_vec()),
+    ]);
+    let entries = entries(&tx);
+    assert_eq!(entries.len(), 2, "{entries:?}");
+
+    let commitments: Vec<_> = tx.commitments().iter().collect();
+    for (entry, index) in entries.iter().zip([0u32, 2]) {
+        assert_eq!(entry.get("index"), Some(&Value::from(index)));
+        let commitment = commitments[index as usize];
+        assert_eq!(entry.get("codeHash"), Some(&Value::from(commitment.code_hash.to_string())));
+        assert_eq!(entry.get("dataHash"), Some(&Value::from(commitment.data_hash.to_string())));
+    }
+
+    assert_eq!(entries[0].get("ok"), Some(&Value::from(true)));
+    assert_eq!(entries[0].get("decoded"), Some(&sample.to_js().unwrap()));
+
+    assert_eq!(entries[1].get("ok"), Some(&Value::from(false)));
+    let Some(Value::Error(error)) = entries[1].get("error") else { panic!("{:?}", entries[1]) };
+    assert_ne!(error.stage, Stage::Tx);
+}
+
+#[test]
+fn missing_data_section_fails_only_its_entry () {
+    let sample = common::samples().remove(0);
+    let mut tx = tx(&[("tx_ibc.wasm", encode::encode(&sample).unwrap())]);
+    let code_hash = tx.add_section(Section::Code(Code::new(vec![], Some("tx_ibc.wasm".to_string())))).get_hash();
+    tx.header.batch.insert(TxCommitments { code_hash, data_hash: Hash([1; 32]), memo_hash: Hash::default() });
+    let entries = entries(&tx);
+    assert_eq!(entries[0].get("ok"), Some(&Value::from(true)));
+    assert_eq!(entries[1].get("ok"), Some(&Value::from(false)));
+    let Some(Value::Error(error)) = entries[1].get("error") else { panic!("{:?}", entries[1]) };
+    assert_eq!(error.stage, Stage::Tx);
+}
+
+#[test]
+fn invalid_tx () {
+    let error = decode_tx(b"not a tx").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Borsh);
+    assert_eq!(error.stage, Stage::Tx);
+}
+
+#[test]
+fn hash_is_the_header_hash () {
+    let tx = tx(&[]);
+    assert_eq!(tx_hash(&tx.serialize_to_vec()).unwrap(), tx.header_hash().to_string());
+}
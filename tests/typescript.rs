@@ -0,0 +1,24 @@
+//! Checks that the shipped TypeScript declarations match the schema.
+
+use namada_ibc_decoder::schema;
+
+const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/decoded.d.ts");
+
+#[test]
+fn declarations_are_up_to_date () {
+    let generated = schema::typescript();
+    if std::env::var_os("UPDATE_TYPESCRIPT").is_some() {
+        std::fs::write(PATH, &generated).unwrap();
+    }
+    let shipped = std::fs::read_to_string(PATH).unwrap();
+    assert!(shipped == generated, "{PATH} is out of date; run `just types` to update it");
+}
+
+#[test]
+fn one_interface_per_message_type () {
+    let generated = schema::typescript();
+    for message in schema::MESSAGES {
+        assert!(generated.contains(&format!("  type: \"{}\";\n", message.name)));
+        assert!(generated.contains(&format!("  | {}", message.interface())));
+    }
+}